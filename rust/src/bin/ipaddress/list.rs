@@ -0,0 +1,215 @@
+use ipaddress::IPAddress;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+
+pub static COMMANDS: [&str; 7] = ["aggregate", "union", "intersect", "subtract", "contains", "sort", "dedupe"];
+
+pub static USAGE: &str = "usage: ipaddress COMMAND [--format plain|json|cisco|juniper] [--name NAME] [FILE...]
+
+Reads networks from the FILEs or stdin (-), one per line. Everything
+after # is a comment, blank lines are ignored.
+
+  aggregate FILE...         summarize all networks
+  union FILE...             same as aggregate, for several lists
+  intersect FILE FILE...    networks contained in every list
+  subtract FILE FILE...     networks of the first list minus all others
+  contains ADDRESS FILE...  networks which contain ADDRESS, exits 1 if none
+  sort FILE...              sort the networks
+  dedupe FILE...            drop repeated networks, keeping the order
+
+  --format FORMAT  plain (default), json, cisco or juniper prefix-list
+  --json           same as --format json
+  --name NAME      name of the vendor prefix-list, default PL
+";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Plain,
+    Json,
+    Cisco,
+    Juniper,
+}
+
+struct Options {
+    format: Format,
+    name: String,
+    address: Option<IPAddress>,
+    inputs: Vec<String>,
+}
+
+fn parse_args(cmd: &str, args: Vec<String>) -> Result<Options, String> {
+    let mut ret = Options {
+        format: Format::Plain,
+        name: String::from("PL"),
+        address: None,
+        inputs: Vec::new(),
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => ret.format = Format::Json,
+            "--format" => {
+                ret.format = match args.next() {
+                    Some(ref f) if f == "plain" => Format::Plain,
+                    Some(ref f) if f == "json" => Format::Json,
+                    Some(ref f) if f == "cisco" => Format::Cisco,
+                    Some(ref f) if f == "juniper" => Format::Juniper,
+                    Some(f) => return Err(format!("unknown format {}", f)),
+                    None => return Err(String::from("--format needs a format")),
+                }
+            }
+            "--name" => ret.name = args.next().ok_or("--name needs a name")?,
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ if cmd == "contains" && ret.address.is_none() => {
+                ret.address = Some(IPAddress::parse(arg.as_str()).map_err(|e| format!("{}: {}", arg, e))?);
+            }
+            _ => ret.inputs.push(arg),
+        }
+    }
+    if cmd == "contains" && ret.address.is_none() {
+        return Err(String::from("contains needs an address"));
+    }
+    if (cmd == "intersect" || cmd == "subtract") && ret.inputs.len() < 2 {
+        return Err(format!("{} needs at least two lists", cmd));
+    }
+    if ret.inputs.is_empty() {
+        ret.inputs.push(String::from("-"));
+    }
+    Ok(ret)
+}
+
+fn read_input(path: &str) -> Result<String, String> {
+    let mut ret = String::new();
+    let res = if path == "-" {
+        io::stdin().read_to_string(&mut ret)
+    } else {
+        File::open(path).and_then(|mut f| f.read_to_string(&mut ret))
+    };
+    res.map_err(|e| format!("{}: {}", path, e))?;
+    Ok(ret)
+}
+
+///  Strips comments and blank lines, every remaining line
+///  has to be an address or network. All malformed lines are
+///  reported with their line number.
+fn parse_list(path: &str, text: &str, errors: &mut Vec<String>) -> Vec<String> {
+    let mut ret = Vec::new();
+    for (lineno, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        match IPAddress::parse(line) {
+            Ok(_) => ret.push(String::from(line)),
+            Err(err) => errors.push(format!("{}:{}: {}", path, lineno + 1, err)),
+        }
+    }
+    ret
+}
+
+fn intersect(a: &[IPAddress], b: &[IPAddress]) -> Vec<IPAddress> {
+    let mut ret = Vec::new();
+    for i in a {
+        for j in b {
+            if let Some(net) = i.intersection(j) {
+                ret.push(net);
+            }
+        }
+    }
+    IPAddress::summarize(&ret)
+}
+
+fn subtract(a: &[IPAddress], b: &[IPAddress]) -> Vec<IPAddress> {
+    let mut ret = Vec::new();
+    for i in a {
+        ret.extend(i.exclude(b));
+    }
+    IPAddress::summarize(&ret)
+}
+
+fn dedupe(list: Vec<IPAddress>) -> Vec<IPAddress> {
+    let mut seen = BTreeSet::new();
+    list.into_iter().filter(|ip| seen.insert(ip.network())).collect()
+}
+
+fn render(list: &[IPAddress], format: Format, name: &str) -> String {
+    let mut ret = String::new();
+    match format {
+        Format::Plain => {
+            for ip in list {
+                ret.push_str(&format!("{}\n", ip.to_string()));
+            }
+        }
+        Format::Json => {
+            let items = list.iter().map(|i| format!("\"{}\"", i.to_string())).collect::<Vec<_>>();
+            ret.push_str(&format!("[{}]\n", items.join(",")));
+        }
+        Format::Cisco => {
+            let (mut seq4, mut seq6) = (0, 0);
+            for ip in list {
+                if ip.is_ipv4() {
+                    seq4 += 5;
+                    ret.push_str(&format!("ip prefix-list {} seq {} permit {}\n", name, seq4, ip.to_string()));
+                } else {
+                    seq6 += 5;
+                    ret.push_str(&format!("ipv6 prefix-list {} seq {} permit {}\n", name, seq6, ip.to_string()));
+                }
+            }
+        }
+        Format::Juniper => {
+            for ip in list {
+                ret.push_str(&format!("set policy-options prefix-list {} {}\n", name, ip.to_string()));
+            }
+        }
+    }
+    ret
+}
+
+///  Runs a list command, returns the output and whether the
+///  command found anything (only relevant for contains).
+pub fn run(cmd: &str, args: Vec<String>) -> Result<(String, bool), String> {
+    let opts = parse_args(cmd, args)?;
+    let mut errors = Vec::new();
+    let mut lists = Vec::new();
+    for path in opts.inputs.iter() {
+        let text = read_input(path)?;
+        lists.push(parse_list(path, &text, &mut errors));
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    let result = match cmd {
+        "aggregate" | "union" => IPAddress::summarize_str(lists.concat())?,
+        _ => {
+            let mut nets = Vec::new();
+            for list in lists {
+                nets.push(IPAddress::to_ipaddress_vec(list)?);
+            }
+            match cmd {
+                "intersect" => {
+                    let first = nets.remove(0);
+                    nets.iter().fold(IPAddress::summarize(&first), |acc, i| intersect(&acc, i))
+                }
+                "subtract" => {
+                    let first = nets.remove(0);
+                    subtract(&first, &nets.concat())
+                }
+                "contains" => {
+                    let address = opts.address.as_ref().unwrap();
+                    nets.concat().into_iter().filter(|i| i.includes(address)).collect()
+                }
+                "sort" => {
+                    let mut ret = nets.concat();
+                    ret.sort();
+                    ret
+                }
+                "dedupe" => dedupe(nets.concat()),
+                _ => return Err(format!("unknown command {}", cmd)),
+            }
+        }
+    };
+    let found = cmd != "contains" || !result.is_empty();
+    Ok((render(&result, opts.format, &opts.name), found))
+}
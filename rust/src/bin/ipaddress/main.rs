@@ -0,0 +1,151 @@
+extern crate ipaddress;
+
+use ipaddress::IPAddress;
+use ipaddress::subnet_info::SubnetInfo;
+use std::env;
+use std::process;
+
+mod list;
+
+static USAGE: &str = "usage: ipaddress [--json] [--split N | --subnet /P | --supernet /P] ADDRESS...
+       ipaddress COMMAND [OPTIONS] [FILE...]
+
+Prints address, netmask, wildcard, network, broadcast, host range,
+host count and reverse zones of every ADDRESS.
+
+  --split N      split the network into N subnets
+  --subnet /P    list the subnets of the network with prefix P
+  --supernet /P  widen the network to prefix P
+  --json         print a JSON array instead of text
+  -h, --help     print this help
+
+Run ipaddress COMMAND --help for the list commands aggregate, union,
+intersect, subtract, contains, sort and dedupe.
+";
+
+enum Transform {
+    Split(usize),
+    Subnet(usize),
+    Supernet(usize),
+}
+
+// why run did not produce a report
+enum Failure {
+    Help,
+    Error(String),
+}
+
+impl From<String> for Failure {
+    fn from(err: String) -> Failure {
+        Failure::Error(err)
+    }
+}
+
+struct Options {
+    json: bool,
+    transform: Option<Transform>,
+    addresses: Vec<String>,
+}
+
+fn parse_prefix(opt: &str, value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| format!("{} needs a prefix", opt))?;
+    let trimmed = value.trim_start_matches('/');
+    trimmed.parse::<usize>().map_err(|_| format!("{}: invalid prefix {}", opt, value))
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, Failure> {
+    let mut ret = Options {
+        json: false,
+        transform: None,
+        addresses: Vec::new(),
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let transform = match arg.as_str() {
+            "--json" => {
+                ret.json = true;
+                continue;
+            }
+            "-h" | "--help" => return Err(Failure::Help),
+            "--split" => {
+                let value = args.next().ok_or_else(|| String::from("--split needs a count"))?;
+                let count = value.parse::<usize>().map_err(|_| format!("--split: invalid count {}", value))?;
+                Transform::Split(count)
+            }
+            "--subnet" => Transform::Subnet(parse_prefix("--subnet", args.next())?),
+            "--supernet" => Transform::Supernet(parse_prefix("--supernet", args.next())?),
+            _ if arg.starts_with("--") => return Err(Failure::Error(format!("unknown option {}", arg))),
+            _ => {
+                ret.addresses.push(arg);
+                continue;
+            }
+        };
+        if ret.transform.is_some() {
+            return Err(Failure::Error(String::from("only one of --split, --subnet and --supernet is allowed")));
+        }
+        ret.transform = Some(transform);
+    }
+    if ret.addresses.is_empty() {
+        return Err(Failure::Error(String::from("no address given")));
+    }
+    Ok(ret)
+}
+
+fn apply(ip: &IPAddress, transform: &Option<Transform>) -> Result<Vec<IPAddress>, String> {
+    match *transform {
+        None => Ok(vec![ip.clone()]),
+        Some(Transform::Split(count)) => ip.split(count),
+        Some(Transform::Subnet(prefix)) => ip.subnet(prefix),
+        Some(Transform::Supernet(prefix)) => ip.supernet(prefix).map(|i| vec![i]),
+    }
+}
+
+fn run(args: Vec<String>) -> Result<String, Failure> {
+    let opts = parse_args(args)?;
+    let mut reports = Vec::new();
+    for addr in opts.addresses.iter() {
+        let ip = IPAddress::parse(addr.as_str()).map_err(|e| format!("{}: {}", addr, e))?;
+        for net in apply(&ip, &opts.transform).map_err(|e| format!("{}: {}", addr, e))? {
+            reports.push(SubnetInfo::new(&net));
+        }
+    }
+    if opts.json {
+        let json = reports.iter().map(|i| i.to_json()).collect::<Vec<_>>();
+        return Ok(format!("[{}]\n", json.join(",")));
+    }
+    let text = reports.iter().map(|i| i.to_text()).collect::<Vec<_>>();
+    Ok(text.join("\n"))
+}
+
+fn main() {
+    let mut args = env::args().skip(1).collect::<Vec<String>>();
+    let cmd = args.first().cloned().unwrap_or_default();
+    if list::COMMANDS.contains(&cmd.as_str()) {
+        args.remove(0);
+        if args.iter().any(|i| i == "-h" || i == "--help") {
+            print!("{}", list::USAGE);
+            return;
+        }
+        match list::run(&cmd, args) {
+            Ok((out, found)) => {
+                print!("{}", out);
+                if !found {
+                    process::exit(1);
+                }
+            }
+            Err(err) => {
+                eprintln!("ipaddress {}: {}", cmd, err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+    match run(args) {
+        Ok(out) => print!("{}", out),
+        Err(Failure::Help) => print!("{}", USAGE),
+        Err(Failure::Error(err)) => {
+            eprintln!("ipaddress: {}\n\n{}", err, USAGE);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//!  Smallest single network containing a set of addresses.
+//!
+//!  Unlike summarize, which never adds addresses that were not
+//!  in the input, a covering supernet is one network per
+//!  family and may contain addresses which were not asked for.
+//!  The report tells how many.
+//!
+//!    let report = covering::report(&IPAddress::to_ipaddress_vec(
+//!        vec!["10.0.0.1", "10.0.0.6"]).unwrap());
+//!
+//!    report[0].network
+//!      ///  10.0.0.0/29
+//!    report[0].aggregate
+//!      ///  ["10.0.0.1/32", "10.0.0.6/32"]
+//!    report[0].extra
+//!      ///  6
+
+use ipaddress::IPAddress;
+use ip_bits::IpVersion;
+use num::bigint::BigUint;
+use std::collections::BTreeMap;
+use num_traits::identities::Zero;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Covering {
+    ///  the smallest network containing all inputs of a family
+    pub network: IPAddress,
+    ///  the exact aggregate of the inputs, see summarize
+    pub aggregate: Vec<IPAddress>,
+    ///  number of addresses in the aggregate
+    pub covered: BigUint,
+    ///  number of addresses in network which are not covered
+    ///  by any input
+    pub extra: BigUint,
+}
+
+///  Returns the smallest network containing all the given
+///  networks, which have to be of one family
+fn cover_family(networks: &[IPAddress]) -> IPAddress {
+    let low = networks.iter().map(|i| i.network()).min_by(|a, b| a.host_address.cmp(&b.host_address)).unwrap();
+    let high = networks.iter().map(|i| i.broadcast()).max_by(|a, b| a.host_address.cmp(&b.host_address)).unwrap();
+    let prefix = IPAddress::common_prefix_len(&low, &high);
+    low.change_prefix(prefix).unwrap().network()
+}
+
+///  Returns one Covering per address family present in
+///  +networks+, IPv4 first, then IPv6, then custom families
+///  by name.
+pub fn report(networks: &[IPAddress]) -> Vec<Covering> {
+    let mut families: BTreeMap<IpVersion, Vec<IPAddress>> = BTreeMap::new();
+    for ip in networks {
+        families.entry(ip.ip_bits.version).or_default().push(ip.clone());
+    }
+    let mut ret = Vec::new();
+    for family in families.values() {
+        let network = cover_family(family);
+        let aggregate = IPAddress::summarize(family);
+        let covered = aggregate.iter().fold(BigUint::zero(), |acc, i| acc + i.size());
+        ret.push(Covering {
+            extra: network.size() - &covered,
+            network,
+            aggregate,
+            covered,
+        });
+    }
+    ret
+}
@@ -0,0 +1,210 @@
+//!  Extracts IPv4 and IPv6 addresses and networks from free
+//!  text like logs, mails or tickets.
+//!
+//!    let text = "deny 10.0.0.0/8 from [2001:db8::1]:443, ignore 1.2.3.4.5";
+//!    for m in extract::extract(text) {
+//!        println!("{} {}..{}", m.address.to_string(), m.start, m.end);
+//!    }
+//!      ///  10.0.0.0/8 5..15
+//!      ///  2001:db8::1/128 22..33
+//!
+//!  Candidates are runs of hex digits, dots and colons. An
+//!  IPv4 address needs four dotted decimals and is not part of
+//!  a longer dotted sequence like 1.2.3.4.5, letters around it
+//!  are fine (foobar172.16.10.1barbaz). An IPv6 address must
+//!  not touch letters or digits, so std::vector or 12:30:45 do
+//!  not match. A bare :: with whitespace on both sides is
+//!  prose (foo :: Int), (::) or ::/0 still match. Trailing
+//!  punctuation, brackets and ports are not part of the match.
+
+use ipaddress::IPAddress;
+
+///  What the extractor reads besides the address
+///
+///  * prefixes, a /len suffix makes the match a network,
+///    otherwise it is left out and the address is a host
+///  * mapped, IPv6 forms with embedded IPv4 like
+///    ::ffff:10.0.0.1, otherwise they are skipped
+///  * zone_ids, a %eth0 suffix of an IPv6 address goes into
+///    Match::zone, otherwise it is left out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    pub prefixes: bool,
+    pub mapped: bool,
+    pub zone_ids: bool,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options { prefixes: true, mapped: true, zone_ids: true }
+    }
+}
+
+///  An address found in the text, +start+..+end+ is its byte
+///  span including prefix and zone id
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub address: IPAddress,
+    pub zone: Option<String>,
+}
+
+impl Match {
+    pub fn as_str<'a>(&self, text: &'a str) -> &'a str {
+        &text[self.start..self.end]
+    }
+}
+
+pub fn extract(text: &str) -> Matches<'_> {
+    extract_with(text, Options::default())
+}
+
+pub fn extract_with(text: &str, options: Options) -> Matches<'_> {
+    Matches { text, options, pos: 0, pending: Vec::new() }
+}
+
+///  Lazy iterator over the matches of a text
+pub struct Matches<'a> {
+    text: &'a str,
+    options: Options,
+    pos: usize,
+    // matches found in the current candidate, last first
+    pending: Vec<Match>,
+}
+
+fn is_candidate(b: u8) -> bool {
+    b.is_ascii_hexdigit() || b == b'.' || b == b':'
+}
+
+fn is_word(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+impl<'a> Matches<'a> {
+    fn byte(&self, i: usize) -> Option<u8> {
+        self.text.as_bytes().get(i).cloned()
+    }
+
+    // end of the run of bytes matching +f+ from +start+
+    fn run(&self, start: usize, f: fn(u8) -> bool) -> usize {
+        let bytes = self.text.as_bytes();
+        (start..bytes.len()).find(|&i| !f(bytes[i])).unwrap_or(bytes.len())
+    }
+
+    // a /len suffix at +end+ which parses together with the
+    // address +core+, returns the network and the new end
+    fn prefix(&self, core: &str, end: usize) -> Option<(IPAddress, usize)> {
+        if !self.options.prefixes || self.byte(end) != Some(b'/') {
+            return None;
+        }
+        let digits_end = self.run(end + 1, |b| b.is_ascii_digit());
+        if digits_end == end + 1 || digits_end - end > 4 || self.byte(digits_end).map_or(false, is_word) {
+            return None;
+        }
+        IPAddress::parse(format!("{}{}", core, &self.text[end..digits_end])).ok().map(|ip| (ip, digits_end))
+    }
+
+    fn ipv4(&self, start: usize, end: usize) -> Option<Match> {
+        let bytes = self.text.as_bytes();
+        let s = (start..end).find(|&i| bytes[i].is_ascii_digit())?;
+        let e = (s..end).rev().find(|&i| bytes[i].is_ascii_digit())? + 1;
+        // part of a longer dotted sequence like 1.2.3.4.x or
+        // v.1.2.3.4, an ellipsis like 10.0.0.1... is punctuation
+        if (s > 1 && bytes[s - 1] == b'.' && is_word(bytes[s - 2])) ||
+           (self.byte(e) == Some(b'.') && self.byte(e + 1).map_or(false, is_word)) {
+            return None;
+        }
+        let core = &self.text[s..e];
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.len() != 4 || parts.iter().any(|p| p.is_empty() || p.len() > 3 || !p.bytes().all(|b| b.is_ascii_digit())) {
+            return None;
+        }
+        let address = IPAddress::parse(core).ok()?;
+        let (address, e) = if e == end { self.prefix(core, e) } else { None }.unwrap_or((address, e));
+        Some(Match { start: s, end: e, address, zone: None })
+    }
+
+    fn ipv6(&self, start: usize, end: usize) -> Option<Match> {
+        let bytes = self.text.as_bytes();
+        let mut s = start;
+        let mut e = end;
+        // a single colon in front or behind is punctuation
+        if bytes[s] == b':' && bytes.get(s + 1) != Some(&b':') {
+            s += 1;
+        }
+        while e > s && (bytes[e - 1] == b'.' || (bytes[e - 1] == b':' && (e - s < 2 || bytes[e - 2] != b':'))) {
+            e -= 1;
+        }
+        if e <= s || (s > 0 && is_word(bytes[s - 1])) || self.byte(e).map_or(false, is_word) {
+            return None;
+        }
+        let core = &self.text[s..e];
+        // a bare :: between whitespace is prose, as in a :: b
+        let spaced = |b: Option<u8>| b.map_or(false, |b| b.is_ascii_whitespace());
+        if !core.bytes().any(|b| b.is_ascii_hexdigit())
+            && spaced(s.checked_sub(1).map(|i| bytes[i])) && spaced(self.byte(e)) {
+            return None;
+        }
+        let mut address = IPAddress::parse(core).ok()?;
+        let mut zone = None;
+        let mut m_end = e;
+        if self.options.zone_ids && self.byte(e) == Some(b'%') {
+            let zone_end = self.run(e + 1, |b| is_word(b) || b == b'-' || b == b'.');
+            let zone_end = (e + 1..zone_end).rev().find(|&i| bytes[i] != b'.').map_or(e + 1, |i| i + 1);
+            if zone_end > e + 1 {
+                zone = Some(self.text[e + 1..zone_end].to_string());
+                m_end = zone_end;
+            }
+        }
+        if let Some((net, prefix_end)) = self.prefix(core, m_end) {
+            address = net;
+            m_end = prefix_end;
+        }
+        Some(Match { start: s, end: m_end, address, zone })
+    }
+
+    // fills pending with the matches in the candidate
+    fn scan(&mut self, start: usize, end: usize) {
+        let mut found = Vec::new();
+        if self.text[start..end].contains(':') {
+            match self.ipv6(start, end) {
+                Some(m) => {
+                    if self.options.mapped || m.address.mapped.is_none() {
+                        found.push(m);
+                    }
+                }
+                // not IPv6, maybe IPv4 with a port or in a list
+                None => {
+                    let mut s = start;
+                    for part in self.text[start..end].split(':') {
+                        found.extend(self.ipv4(s, s + part.len()));
+                        s += part.len() + 1;
+                    }
+                }
+            }
+        } else {
+            found.extend(self.ipv4(start, end));
+        }
+        if let Some(last) = found.last() {
+            self.pos = self.pos.max(last.end);
+        }
+        found.reverse();
+        self.pending = found;
+    }
+}
+
+impl<'a> Iterator for Matches<'a> {
+    type Item = Match;
+    fn next(&mut self) -> Option<Match> {
+        loop {
+            if let Some(m) = self.pending.pop() {
+                return Some(m);
+            }
+            let start = self.pos + self.text.as_bytes()[self.pos..].iter().position(|&b| is_candidate(b))?;
+            let end = self.run(start, is_candidate);
+            self.pos = end;
+            self.scan(start, end);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//!  Address families besides IPv4 and IPv6.
+//!
+//!  IpBits describes how wide an address is and how it is
+//!  written, an AddressFamily plugs a custom description into
+//!  it. The resulting IPAddress values share the prefix math of
+//!  the IP families: network, broadcast, includes, subnet,
+//!  supernet, split, aggregate, summarize, range_to_networks,
+//!  exclude and intersection work unchanged.
+//!
+//!    let oui = family::parse(&family::EUI48, "00:1b:21:00:00:00/24").unwrap();
+//!    oui.includes(&family::parse(&family::EUI48, "00:1b:21:3a:4f:5c").unwrap())
+//!      ///  true
+//!
+//!  Addresses of a custom family are neither private nor
+//!  loopback, to_ipv6 returns them unchanged, they have no
+//!  reverse DNS zone and they can not be serialized.
+
+use num::bigint::BigUint;
+use num_traits::identities::{One, Zero};
+use num_traits::Num;
+use std::panic::RefUnwindSafe;
+
+use ip_bits::{IpBits, IpVersion};
+use prefix::Prefix;
+use IPAddress;
+
+///  Describes the width, text format and parsing of an
+///  address family. +bits+ must be at most 128 and a multiple
+///  of +part_bits+, +part_bits+ at most 16, the parts are what
+///  IPAddress::parts returns. Families are shared as
+///  &'static references, RefUnwindSafe keeps IPAddress
+///  usable with catch_unwind.
+pub trait AddressFamily: Sync + RefUnwindSafe {
+    ///  Identifies the family together with bits, addresses
+    ///  of families which differ in either never compare equal
+    fn name(&self) -> &'static str;
+    fn bits(&self) -> usize;
+    fn part_bits(&self) -> usize {
+        8
+    }
+    ///  Writes an address without prefix
+    fn format(&self, addr: &BigUint) -> String;
+    fn format_uncompressed(&self, addr: &BigUint) -> String {
+        self.format(addr)
+    }
+    ///  Reads an address without prefix
+    fn parse(&self, s: &str) -> Result<BigUint, String>;
+}
+
+fn family(ip_bits: &IpBits) -> &'static dyn AddressFamily {
+    match ip_bits.version {
+        IpVersion::Custom(family) => family,
+        _ => unreachable!(),
+    }
+}
+
+fn as_compressed(ip_bits: &IpBits, addr: &BigUint) -> String {
+    family(ip_bits).format(addr)
+}
+
+fn as_uncompressed(ip_bits: &IpBits, addr: &BigUint) -> String {
+    family(ip_bits).format_uncompressed(addr)
+}
+
+fn is_private(_: &IPAddress) -> bool {
+    false
+}
+
+fn is_loopback(_: &IPAddress) -> bool {
+    false
+}
+
+fn to_ipv6(ia: &IPAddress) -> IPAddress {
+    ia.clone()
+}
+
+pub fn ip_bits(family: &'static dyn AddressFamily) -> IpBits {
+    IpBits {
+        version: IpVersion::Custom(family),
+        vt_as_compressed_string: as_compressed,
+        vt_as_uncompressed_string: as_uncompressed,
+        bits: family.bits(),
+        part_bits: family.part_bits(),
+        dns_bits: 4,
+        rev_domain: "",
+        part_mod: BigUint::one() << family.part_bits(),
+        host_ofs: BigUint::zero(),
+    }
+}
+
+///  Creates a prefix of +num+ bits for +family+
+///
+///    family::prefix(&family::EUI48, 24).unwrap().to_ip_str()
+///      ///  "ff:ff:ff:00:00:00"
+///
+pub fn prefix(family: &'static dyn AddressFamily, num: usize) -> Result<Prefix, String> {
+    Prefix::new(IpVersion::Custom(family), num)
+}
+
+pub fn from_int(family: &'static dyn AddressFamily, addr: BigUint, prefix_num: usize) -> Result<IPAddress, String> {
+    if addr >= BigUint::one() << family.bits() {
+        return Err(format!("{} does not fit into a {} address", addr, family.name()));
+    }
+    Ok(IPAddress {
+        ip_bits: ip_bits(family),
+        host_address: addr,
+        prefix: prefix(family, prefix_num)?,
+        mapped: None,
+        vt_is_private: is_private,
+        vt_is_loopback: is_loopback,
+        vt_to_ipv6: to_ipv6,
+    })
+}
+
+///  Parses +str+ as an address of +family+ with an optional
+///  prefix length, without one the address is a single host
+pub fn parse<S: Into<String>>(family: &'static dyn AddressFamily, str: S) -> Result<IPAddress, String> {
+    let str = str.into();
+    let (addr, netmask) = IPAddress::split_at_slash(&str);
+    let num = match netmask {
+        Some(num) => num.parse::<usize>().map_err(|_| format!("Invalid prefix {}", str))?,
+        None => family.bits(),
+    };
+    from_int(family, family.parse(&addr)?, num)
+}
+
+fn format_octets(addr: &BigUint, octets: usize) -> String {
+    let hex = format!("{:0>width$}", addr.to_str_radix(16), width = octets * 2);
+    let mut ret = String::new();
+    for (i, c) in hex.chars().enumerate() {
+        if i > 0 && i % 2 == 0 {
+            ret.push(':');
+        }
+        ret.push(c);
+    }
+    ret
+}
+
+// accepts 00:1b:21:3a:4f:5c, 00-1b-21-3a-4f-5c, 001b.213a.4f5c
+// and 001b213a4f5c, every group must have the same width
+fn parse_octets(name: &str, s: &str, octets: usize) -> Result<BigUint, String> {
+    let groups: Vec<&str> = s.split(&[':', '-', '.'][..]).collect();
+    let width = groups[0].len();
+    if width == 0
+        || groups.len() * width != octets * 2
+        || groups.iter().any(|g| g.len() != width || !g.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return Err(format!("Invalid {} {}", name, s));
+    }
+    Ok(BigUint::from_str_radix(&groups.concat(), 16).unwrap())
+}
+
+///  48 bit MAC addresses, written as 00:1b:21:3a:4f:5c
+pub struct Eui48;
+
+impl AddressFamily for Eui48 {
+    fn name(&self) -> &'static str {
+        "EUI-48"
+    }
+    fn bits(&self) -> usize {
+        48
+    }
+    fn format(&self, addr: &BigUint) -> String {
+        format_octets(addr, 6)
+    }
+    fn parse(&self, s: &str) -> Result<BigUint, String> {
+        parse_octets(self.name(), s, 6)
+    }
+}
+
+///  64 bit extended identifiers, written as
+///  02:1b:21:ff:fe:3a:4f:5c
+pub struct Eui64;
+
+impl AddressFamily for Eui64 {
+    fn name(&self) -> &'static str {
+        "EUI-64"
+    }
+    fn bits(&self) -> usize {
+        64
+    }
+    fn format(&self, addr: &BigUint) -> String {
+        format_octets(addr, 8)
+    }
+    fn parse(&self, s: &str) -> Result<BigUint, String> {
+        parse_octets(self.name(), s, 8)
+    }
+}
+
+pub static EUI48: Eui48 = Eui48;
+pub static EUI64: Eui64 = Eui64;
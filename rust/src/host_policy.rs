@@ -0,0 +1,122 @@
+use ipaddress::IPAddress;
+use num::bigint::BigUint;
+use num_traits::identities::{One, Zero};
+use num_traits::FromPrimitive;
+
+///  Defines which addresses of a network are usable hosts.
+///
+///  A policy reserves a number of addresses at the start
+///  (head) and at the end (tail) of a network, everything in
+///  between is usable.
+///
+///  * Classic reserves the network and broadcast address of
+///    IPv4 networks (ip_bits.host_ofs), IPv6 networks are
+///    fully usable.
+///  * Rfc3021 is Classic, except that /31 and /32 IPv4
+///    networks (RFC 3021) and /127 and /128 IPv6 networks
+///    (RFC 6164) are point to point links where every address
+///    is usable.
+///  * SubnetRouterAnycast is Rfc3021, but also excludes the
+///    IPv6 subnet-router anycast address, which is the first
+///    address of every IPv6 network larger than a /127.
+///  * Reserved excludes a fixed number of addresses, for
+///    example the first four and the last one of a cloud
+///    provider subnet.
+///
+///  Example:
+///
+///    let ip = IPAddress::parse("10.0.0.0/31").unwrap();
+///
+///    ip.host_count_with(&HostPolicy::Classic)
+///      ///  0
+///    ip.host_count_with(&HostPolicy::Rfc3021)
+///      ///  2
+///    IPAddress::parse("10.0.0.0/24").unwrap()
+///      .host_count_with(&HostPolicy::Reserved { head: 4, tail: 1 })
+///      ///  251
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HostPolicy {
+    #[default]
+    Classic,
+    Rfc3021,
+    SubnetRouterAnycast,
+    Reserved { head: usize, tail: usize },
+}
+
+impl HostPolicy {
+    ///  Returns the number of reserved addresses at the start
+    ///  and at the end of the network of +net+
+    pub fn reserved(&self, net: &IPAddress) -> (BigUint, BigUint) {
+        let host_ofs = net.ip_bits.host_ofs.clone();
+        let point_to_point = net.prefix.host_prefix() <= 1;
+        match *self {
+            HostPolicy::Classic => (host_ofs.clone(), host_ofs),
+            HostPolicy::Rfc3021 => {
+                if point_to_point {
+                    (BigUint::zero(), BigUint::zero())
+                } else {
+                    (host_ofs.clone(), host_ofs)
+                }
+            }
+            HostPolicy::SubnetRouterAnycast => {
+                if point_to_point {
+                    (BigUint::zero(), BigUint::zero())
+                } else if net.is_ipv6() {
+                    (BigUint::one(), BigUint::zero())
+                } else {
+                    (host_ofs.clone(), host_ofs)
+                }
+            }
+            HostPolicy::Reserved { head, tail } => {
+                (BigUint::from_usize(head).unwrap(), BigUint::from_usize(tail).unwrap())
+            }
+        }
+    }
+
+    ///  Returns the number of usable addresses in the
+    ///  network of +net+
+    pub fn host_count(&self, net: &IPAddress) -> BigUint {
+        let (head, tail) = self.reserved(net);
+        let reserved = head + tail;
+        let size = net.size();
+        if reserved >= size {
+            return BigUint::zero();
+        }
+        size - reserved
+    }
+
+    ///  Returns the first usable address of the network of
+    ///  +net+, None if the network has no usable address
+    pub fn first(&self, net: &IPAddress) -> Option<IPAddress> {
+        if self.host_count(net).is_zero() {
+            return None;
+        }
+        let (head, _) = self.reserved(net);
+        Some(net.from(&(net.network().host_address + head), &net.prefix))
+    }
+
+    ///  Returns the last usable address of the network of
+    ///  +net+, None if the network has no usable address
+    pub fn last(&self, net: &IPAddress) -> Option<IPAddress> {
+        if self.host_count(net).is_zero() {
+            return None;
+        }
+        let (_, tail) = self.reserved(net);
+        Some(net.from(&(net.broadcast().host_address - tail), &net.prefix))
+    }
+
+    ///  Checks whether +addr+ is a usable address of the
+    ///  network of +net+
+    pub fn is_usable(&self, net: &IPAddress, addr: &IPAddress) -> bool {
+        if !net.is_same_kind(addr) {
+            return false;
+        }
+        match (self.first(net), self.last(net)) {
+            (Some(first), Some(last)) => {
+                first.host_address <= addr.host_address && addr.host_address <= last.host_address
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,27 +1,107 @@
+#[cfg(feature = "std")]
 use num::bigint::BigUint;
 
 
+#[cfg(feature = "std")]
 use num_traits::identities::One;
+#[cfg(feature = "std")]
 use num_traits::identities::Zero;
 // use num_integer::Integer;
+#[cfg(feature = "std")]
 use num_traits::cast::ToPrimitive;
+#[cfg(feature = "std")]
 use num_traits::FromPrimitive;
-use core::convert::From;
+#[cfg(feature = "std")]
 use core::ops::Rem;
+#[cfg(feature = "std")]
 use core::ops::Shr;
 // use core::marker::Copy;
 use core::clone::Clone;
-use std::fmt;
+use core::cmp::Ordering;
+use core::fmt;
+#[cfg(feature = "std")]
 use rle;
+#[cfg(feature = "std")]
+use family::AddressFamily;
 
 #[allow(dead_code)]
-#[derive(PartialOrd, PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(Clone, Copy)]
+#[non_exhaustive]
 pub enum IpVersion {
     V4,
     V6,
+    // a family::AddressFamily, identified by its name
+    #[cfg(feature = "std")]
+    Custom(&'static dyn AddressFamily),
+}
+
+impl IpVersion {
+    ///  Width of an address in bits
+    pub fn bits(&self) -> usize {
+        match *self {
+            IpVersion::V4 => 32,
+            IpVersion::V6 => 128,
+            #[cfg(feature = "std")]
+            IpVersion::Custom(family) => family.bits(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            IpVersion::V4 => "IPv4",
+            IpVersion::V6 => "IPv6",
+            #[cfg(feature = "std")]
+            IpVersion::Custom(family) => family.name(),
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match *self {
+            IpVersion::V4 => 0,
+            IpVersion::V6 => 1,
+            #[cfg(feature = "std")]
+            IpVersion::Custom(_) => 2,
+        }
+    }
+}
+
+// V4 < V6 < custom families ordered by name and width
+impl Ord for IpVersion {
+    fn cmp(&self, other: &IpVersion) -> Ordering {
+        self.rank().cmp(&other.rank())
+            .then_with(|| self.name().cmp(other.name()))
+            .then_with(|| self.bits().cmp(&other.bits()))
+    }
+}
+
+impl PartialOrd for IpVersion {
+    fn partial_cmp(&self, other: &IpVersion) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for IpVersion {
+    fn eq(&self, other: &IpVersion) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for IpVersion {}
+
+impl fmt::Debug for IpVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IpVersion::V4 => f.write_str("V4"),
+            IpVersion::V6 => f.write_str("V6"),
+            #[cfg(feature = "std")]
+            IpVersion::Custom(family) => write!(f, "Custom({})", family.name()),
+        }
+    }
 }
 
 // #[derive(Debug, Clone)]
+#[cfg(feature = "std")]
+#[non_exhaustive]
 pub struct IpBits {
     pub version: IpVersion,
     pub vt_as_compressed_string: fn(&IpBits, &BigUint) -> String,
@@ -34,6 +114,7 @@ pub struct IpBits {
     pub host_ofs: BigUint, // ipv4=1, ipv6=0
 }
 
+#[cfg(feature = "std")]
 impl Clone for IpBits {
     fn clone(&self) -> IpBits {
         IpBits {
@@ -50,6 +131,7 @@ impl Clone for IpBits {
     }
 }
 
+#[cfg(feature = "std")]
 impl fmt::Debug for IpBits {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "IpBits: {:?}", self.version)
@@ -57,6 +139,7 @@ impl fmt::Debug for IpBits {
 }
 
 
+#[cfg(feature = "std")]
 impl IpBits {
     #[allow(unused_variables)]
     pub fn parts(&self, bu: &BigUint) -> Vec<u16> {
@@ -107,13 +190,14 @@ impl IpBits {
 
     pub fn dns_part_format(&self, i: u8) -> String {
         match self.version {
-            IpVersion::V4 => return format!("{}", i),
-            IpVersion::V6 => return format!("{:01x}", i),
+            IpVersion::V4 => format!("{}", i),
+            IpVersion::V6 | IpVersion::Custom(_) => format!("{:01x}", i),
         }
     }
 }
 
 
+#[cfg(feature = "std")]
 fn ipv4_as_compressed(ip_bits: &IpBits, host_address: &BigUint) -> String {
     let mut ret = String::new();
     let mut sep = "";
@@ -124,6 +208,7 @@ fn ipv4_as_compressed(ip_bits: &IpBits, host_address: &BigUint) -> String {
     }
     return ret;
 }
+#[cfg(feature = "std")]
 fn ipv6_as_compressed(ip_bits: &IpBits, host_address: &BigUint) -> String {
     //println!("ipv6_as_compressed:{}", host_address);
     let mut ret = String::new();
@@ -147,6 +232,7 @@ fn ipv6_as_compressed(ip_bits: &IpBits, host_address: &BigUint) -> String {
     }
     return ret;
 }
+#[cfg(feature = "std")]
 fn ipv6_as_uncompressed(ip_bits: &IpBits, host_address: &BigUint) -> String {
     let mut ret = String::new();
     let mut sep = "";
@@ -160,6 +246,7 @@ fn ipv6_as_uncompressed(ip_bits: &IpBits, host_address: &BigUint) -> String {
 
 
 
+#[cfg(feature = "std")]
 pub fn v4() -> IpBits {
     IpBits {
         version: IpVersion::V4,
@@ -174,6 +261,7 @@ pub fn v4() -> IpBits {
     }
 }
 
+#[cfg(feature = "std")]
 pub fn v6() -> IpBits {
     return IpBits {
         version: IpVersion::V6,
@@ -4,6 +4,8 @@ use core::ops::Shl;
 use core::ops::Shr;
 use core::ops::Sub;
 use core::ops::Rem;
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+use core::ops::Index;
 // use core::ops::Rem;
 // use core::ops::Range;
 //use core::iter::Step;
@@ -15,13 +17,15 @@ use core::convert::From;
 
 use num::bigint::BigUint;
 use num::bigint::ToBigUint;
+use num::bigint::{BigInt, Sign};
 // use num_integer::Integer;
 
 use ip_bits::IpBits;
+use host_policy::HostPolicy;
+use netmask::NetmaskSyntax;
 use prefix::Prefix;
-use regex::Regex;
 // use std::f64;
-use std::fmt;
+use core::fmt;
 
 use num_traits::identities::Zero;
 use num_traits::identities::One;
@@ -67,10 +71,7 @@ impl Clone for IPAddress {
 impl Ord for IPAddress {
     fn cmp(&self, oth: & IPAddress) -> Ordering {
             if self.ip_bits.version != oth.ip_bits.version {
-                if self.ip_bits.version == IpVersion::V6 {
-                    return Ordering::Greater;
-                }
-                return Ordering::Less;
+                return self.ip_bits.version.cmp(&oth.ip_bits.version);
             }
             //let adr_diff = self.host_address - oth.host_address;
             if self.host_address < oth.host_address  {
@@ -103,6 +104,114 @@ impl PartialEq for IPAddress {
 
 impl Eq for IPAddress {}
 
+///  Moves the address by +n+ and keeps the prefix, the
+///  operators are implemented for references because the
+///  inherent add and sub of IPAddress take another address.
+///
+///    &IPAddress::parse("10.0.0.1/24").unwrap() + 5
+///      ///  10.0.0.6/24
+///
+///  Panics if the result leaves the address space, use
+///  checked_add or wrapping_add to handle that case.
+impl Add<u128> for &IPAddress {
+    type Output = IPAddress;
+    fn add(self, n: u128) -> IPAddress {
+        self.checked_add(n).expect("address overflow")
+    }
+}
+
+impl Sub<u128> for &IPAddress {
+    type Output = IPAddress;
+    fn sub(self, n: u128) -> IPAddress {
+        self.checked_sub(n).expect("address underflow")
+    }
+}
+
+///  Bitwise operators on the native integer form of two
+///  addresses of the same family, the result keeps the prefix
+///  of the left operand.
+///
+///    &IPAddress::parse("10.1.2.3/8").unwrap() & &IPAddress::parse("255.255.0.0").unwrap()
+///      ///  10.1.0.0/8
+///
+///  Panics if the addresses are of different families.
+impl BitAnd for &IPAddress {
+    type Output = IPAddress;
+    fn bitand(self, other: &IPAddress) -> IPAddress {
+        assert_same_family(self, other);
+        self.rebuild(&(&self.host_address & &other.host_address), &self.prefix)
+    }
+}
+
+impl BitOr for &IPAddress {
+    type Output = IPAddress;
+    fn bitor(self, other: &IPAddress) -> IPAddress {
+        assert_same_family(self, other);
+        self.rebuild(&(&self.host_address | &other.host_address), &self.prefix)
+    }
+}
+
+impl BitXor for &IPAddress {
+    type Output = IPAddress;
+    fn bitxor(self, other: &IPAddress) -> IPAddress {
+        assert_same_family(self, other);
+        self.rebuild(&(&self.host_address ^ &other.host_address), &self.prefix)
+    }
+}
+
+///  Inverts all the bits of the address within the width of
+///  its family.
+impl Not for &IPAddress {
+    type Output = IPAddress;
+    fn not(self) -> IPAddress {
+        self.rebuild(&(&self.host_address ^ &self.max_host_address()), &self.prefix)
+    }
+}
+
+///  The parts of an address, octets for IPv4 and hextets for
+///  IPv6, as returned by to_parts. An IPAddress keeps its
+///  value as a BigUint and has no part it could lend out, so
+///  indexing goes through this array.
+///
+///    let parts = IPAddress::parse("192.168.1.77/24").unwrap().to_parts();
+///    parts[3]
+///      ///  77
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parts {
+    parts: Vec<u16>,
+}
+
+impl Parts {
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[u16] {
+        &self.parts
+    }
+}
+
+///  Panics if +i+ is out of range, like a slice.
+impl Index<usize> for Parts {
+    type Output = u16;
+    fn index(&self, i: usize) -> &u16 {
+        match self.parts.get(i) {
+            Some(part) => part,
+            None => panic!("index {} out of range for {} parts", i, self.parts.len()),
+        }
+    }
+}
+
+fn assert_same_family(a: &IPAddress, b: &IPAddress) {
+    assert!(a.ip_bits.version == b.ip_bits.version,
+            "bitwise operation on {} and {} of different families", a.to_string(), b.to_string());
+}
+
 
 impl IPAddress {
     /// Parse the argument string to create a new
@@ -124,17 +233,21 @@ impl IPAddress {
     ///
     pub fn parse<S: Into<String>>(_str: S) -> Result<IPAddress, String> {
         let str = _str.into();
-        let re_mapped = Regex::new(r":.+\.").unwrap();
-        let re_ipv4 = Regex::new(r"\.").unwrap();
-        let re_ipv6 = Regex::new(r":").unwrap();
-        if re_mapped.is_match(&str) {
+        // mapped addresses have a dot somewhere after the first
+        // colon, a dotted netmask does not count
+        let addr = IPAddress::split_at_slash(&str).0;
+        let is_mapped = match (addr.find(':'), addr.rfind('.')) {
+            (Some(colon), Some(dot)) => dot > colon + 1,
+            _ => false,
+        };
+        if is_mapped {
             // println!("mapped:{}", &str);
             return ::ipv6_mapped::new(str);
         } else {
-            if re_ipv4.is_match(&str) {
+            if addr.contains('.') {
                 // println!("ipv4:{}", &str);
                 return ::ipv4::new(str);
-            } else if re_ipv6.is_match(&str) {
+            } else if addr.contains(':') {
                 // println!("ipv6:{}", &str);
                 return ::ipv6::new(str);
             }
@@ -142,17 +255,39 @@ impl IPAddress {
         return Err(format!("Unknown IP Address {}", str));
     }
 
+    ///  Like parse, NetmaskSyntax::Strict rejects the netmask
+    ///  notations of device configs, see the netmask module
+    ///
+    ///    IPAddress::parse_with("10.0.0.1 255.0.0.0", NetmaskSyntax::Strict)
+    ///      ///  Err("Invalid IP 10.0.0.1 255.0.0.0")
+    ///
+    pub fn parse_with<S: Into<String>>(str: S, syntax: NetmaskSyntax) -> Result<IPAddress, String> {
+        let str = str.into();
+        syntax.check(&str)?;
+        IPAddress::parse(str)
+    }
+
+    ///  Splits an address from its netmask, which follows a
+    ///  slash, a single space or the word mask or netmask
+    ///  between single spaces. Other whitespace stays in the
+    ///  parts and makes them invalid.
+    ///
+    ///    IPAddress::split_at_slash("10.0.0.1 mask 255.0.0.0")
+    ///      ///  ("10.0.0.1", Some("255.0.0.0"))
+    ///
     pub fn split_at_slash(str: &String) -> (String, Option<String>) {
-        let slash : Vec<&str> = str.trim().split("/").collect();
-        let mut addr = String::new();
-        if slash.get(0).is_some() {
-            addr.push_str(slash.get(0).unwrap().to_string().trim());
-        }
-        if slash.get(1).is_some() {
-            return (addr, Some(String::from(slash.get(1).unwrap().to_string().trim())));
-        } else {
-            return (addr, None);
+        let str = str.trim();
+        if !str.contains('/') {
+            let words: Vec<&str> = str.split(' ').collect();
+            match words.as_slice() {
+                [addr, "mask", netmask] | [addr, "netmask", netmask] | [addr, netmask] => {
+                    return (addr.to_string(), Some(netmask.to_string()));
+                }
+                _ => (),
+            }
         }
+        let slash : Vec<&str> = str.split("/").collect();
+        (slash[0].to_string(), slash.get(1).map(|nm| nm.to_string()))
     }
     #[allow(dead_code)]
     pub fn from(&self, addr: &BigUint, prefix: &Prefix) -> IPAddress {
@@ -167,6 +302,18 @@ impl IPAddress {
         };
     }
 
+    // like from, but an IPv6 result gets its IPv4-mapped
+    // address recomputed instead of keeping the one of self
+    fn rebuild(&self, addr: &BigUint, prefix: &Prefix) -> IPAddress {
+        if !self.is_ipv6() {
+            return self.from(addr, prefix);
+        }
+        ::ipv6::from_int(addr.clone(), prefix.num).unwrap_or_else(|_| IPAddress {
+            mapped: None,
+            ..self.from(addr, prefix)
+        })
+    }
+
     /// True if the object is an IPv4 address
     ///
     ///   ip = IPAddress("192.168.10.100/24")
@@ -417,6 +564,99 @@ impl IPAddress {
         return self.ip_bits.parts(&self.host_address);
     }
 
+    ///  Returns part +i+ of the address, counted from the left,
+    ///  which is an octet for IPv4 and a hextet for IPv6. None
+    ///  if +i+ is out of range.
+    ///
+    ///    ip = IPAddress("2001:db8::8:800:200c:417a")
+    ///
+    ///    ip.part(1)
+    ///      ///  Some(0xdb8)
+    ///
+    pub fn part(&self, i: usize) -> Option<u16> {
+        let count = self.ip_bits.bits / self.ip_bits.part_bits;
+        if i >= count {
+            return None;
+        }
+        let shift = (count - 1 - i) * self.ip_bits.part_bits;
+        let mask = (BigUint::one() << self.ip_bits.part_bits) - BigUint::one();
+        ((self.host_address.clone() >> shift) & mask).to_u16()
+    }
+
+    ///  Returns all parts of the address for indexing.
+    ///
+    ///    ip = IPAddress("2001:db8::8:800:200c:417a")
+    ///
+    ///    ip.to_parts()[7]
+    ///      ///  0x417a
+    ///
+    pub fn to_parts(&self) -> Parts {
+        Parts { parts: self.parts() }
+    }
+
+    ///  Returns a copy of the address with part +i+ replaced by
+    ///  +value+, the prefix is kept.
+    ///
+    ///    ip = IPAddress("192.168.1.77/24")
+    ///
+    ///    ip.with_part(3, 254).to_string
+    ///      ///  "192.168.1.254/24"
+    ///
+    pub fn with_part(&self, i: usize, value: u16) -> Result<IPAddress, String> {
+        let count = self.ip_bits.bits / self.ip_bits.part_bits;
+        if i >= count {
+            return Err(format!("index {} out of range for {}", i, self.to_s()));
+        }
+        let mask = (BigUint::one() << self.ip_bits.part_bits) - BigUint::one();
+        let value = BigUint::from_u16(value).unwrap();
+        if value > mask {
+            return Err(format!("{} does not fit into a part of {}", value, self.to_s()));
+        }
+        let shift = (count - 1 - i) * self.ip_bits.part_bits;
+        let cleared = &self.host_address & &(self.max_host_address() ^ (mask << shift));
+        Ok(self.rebuild(&(cleared | (value << shift)), &self.prefix))
+    }
+
+    ///  Returns octet +i+ of an IPv4 address, None for IPv6
+    ///  or if +i+ is out of range
+    pub fn octet(&self, i: usize) -> Option<u8> {
+        if !self.is_ipv4() {
+            return None;
+        }
+        self.part(i).map(|i| i as u8)
+    }
+
+    ///  Returns a copy of an IPv4 address with octet +i+
+    ///  replaced by +value+
+    ///
+    ///    IPAddress("10.1.2.3/8").with_octet(3, 1).to_string
+    ///      ///  "10.1.2.1/8"
+    ///
+    pub fn with_octet(&self, i: usize, value: u8) -> Result<IPAddress, String> {
+        if !self.is_ipv4() {
+            return Err(format!("{} has no octets, use with_hextet", self.to_s()));
+        }
+        self.with_part(i, value as u16)
+    }
+
+    ///  Returns hextet +i+ of an IPv6 address, None for IPv4
+    ///  or if +i+ is out of range
+    pub fn hextet(&self, i: usize) -> Option<u16> {
+        if !self.is_ipv6() {
+            return None;
+        }
+        self.part(i)
+    }
+
+    ///  Returns a copy of an IPv6 address with hextet +i+
+    ///  replaced by +value+
+    pub fn with_hextet(&self, i: usize, value: u16) -> Result<IPAddress, String> {
+        if !self.is_ipv6() {
+            return Err(format!("{} has no hextets, use with_octet", self.to_s()));
+        }
+        self.with_part(i, value)
+    }
+
     pub fn parts_hex_str(&self) -> Vec<String> {
         let mut ret : Vec<String> = Vec::new();
         for i in self.parts() {
@@ -433,8 +673,14 @@ impl IPAddress {
     ///    ip.dns_rev_domains
     ///      // => ["16.172.in-addr.arpa","17.172.in-addr.arpa"]
     ///
+    ///  Families without a reverse zone, like EUI-48, return
+    ///  an empty list.
+    ///
     pub fn dns_rev_domains(&self) -> Vec<String> {
         let mut ret: Vec<String> = Vec::new();
+        if self.ip_bits.rev_domain.is_empty() {
+            return ret;
+        }
         for net in self.dns_networks() {
             // println!("dns_rev_domains:{}:{}", self.to_string(), net.to_string());
             ret.push(net.dns_reverse());
@@ -621,6 +867,21 @@ impl IPAddress {
     pub fn summarize(networks: &Vec<IPAddress>) -> Vec<IPAddress> {
         return IPAddress::aggregate(networks);
     }
+    ///  Returns the smallest single network per family which
+    ///  contains all the given networks, IPv4 first. Unlike
+    ///  summarize the result may contain addresses which are
+    ///  not part of the input, covering::report tells how many.
+    ///
+    ///    ip1 = IPAddress("10.0.0.1")
+    ///    ip2 = IPAddress("10.0.0.6")
+    ///
+    ///    IPAddress::covering_supernet([ip1, ip2]).map(&:to_string)
+    ///      ///  ["10.0.0.0/29"]
+    ///
+    pub fn covering_supernet(networks: &[IPAddress]) -> Vec<IPAddress> {
+        ::covering::report(networks).into_iter().map(|i| i.network).collect()
+    }
+
     pub fn summarize_str<S: Into<String>>(netstr: Vec<S>) -> Result<Vec<IPAddress>, String> {
         let vec = IPAddress::to_ipaddress_vec(netstr);
         if vec.is_err() {
@@ -713,11 +974,16 @@ impl IPAddress {
     }
 
 
+    ///  Returns the prefix of a netmask given as prefix
+    ///  length, as address (255.255.255.0, ffff:ffff::) or in
+    ///  hex (0xffffff00)
     pub fn parse_netmask_to_prefix<S: Into<String>>(_netmask: S) -> Result<usize, String> {
         let my_str = _netmask.into();
-        let is_number = my_str.parse();
-        if is_number.is_ok() {
-            return Ok(is_number.unwrap());
+        if let Some(num) = IPAddress::parse_prefix_len(&my_str) {
+            return num;
+        }
+        if let Some(num) = ::netmask::hex_to_prefix(&my_str, None) {
+            return num;
         }
         let my = IPAddress::parse(my_str.clone());
         if my.is_err() {
@@ -727,6 +993,37 @@ impl IPAddress {
         return IPAddress::netmask_to_prefix(&my_ip.host_address, my_ip.ip_bits.bits);
     }
 
+    // a prefix length is written in decimal without leading
+    // zeros, None if +str+ is not all digits
+    fn parse_prefix_len(str: &str) -> Option<Result<usize, String>> {
+        if str.is_empty() || !str.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        if str.len() > 1 && str.starts_with('0') {
+            return Some(Err(format!("prefix length {} has leading zeros", str)));
+        }
+        Some(str.parse().map_err(|_| format!("Invalid prefix length {}", str)))
+    }
+
+    ///  Like parse_netmask_to_prefix for an address of +bits+
+    ///  bits, a netmask of the other family is an error
+    pub fn parse_netmask_to_prefix_for(netmask: &str, bits: usize) -> Result<usize, String> {
+        if let Some(num) = IPAddress::parse_prefix_len(netmask) {
+            return num;
+        }
+        if let Some(num) = ::netmask::hex_to_prefix(netmask, Some(bits)) {
+            return num;
+        }
+        if bits == 128 && netmask.contains('.') {
+            return Err(String::from("dotted netmask is not valid for IPv6"));
+        }
+        let my_ip = IPAddress::parse(netmask).map_err(|e| format!("illegal netmask {}", e))?;
+        if my_ip.ip_bits.bits != bits {
+            return Err(format!("illegal netmask {} for a {} bit address", netmask, bits));
+        }
+        IPAddress::netmask_to_prefix(&my_ip.host_address, bits)
+    }
+
 
         ///  Set a new prefix number for the object
         ///
@@ -841,6 +1138,145 @@ impl IPAddress {
         return self.host_address.to_str_radix(16);
     }
 
+    ///  Returns the address as u32, None for IPv6
+    ///
+    ///    ip = IPAddress("172.16.10.1/24")
+    ///
+    ///    ip.to_u32()
+    ///      ///  Some(2886732289)
+    ///
+    pub fn to_u32(&self) -> Option<u32> {
+        if !self.is_ipv4() {
+            return None;
+        }
+        self.host_address.to_u32()
+    }
+
+    ///  Returns the address as u128, IPv4 addresses are
+    ///  returned as their 32 bit value
+    pub fn to_u128(&self) -> u128 {
+        let mut bytes = [0u8; 16];
+        let octets = self.host_address.to_bytes_be();
+        bytes[16 - octets.len()..].copy_from_slice(&octets);
+        u128::from_be_bytes(bytes)
+    }
+
+    ///  Returns the address in network byte order, 4 octets
+    ///  for IPv4 and 16 for IPv6. Families which are not a
+    ///  whole number of octets wide are padded with leading
+    ///  zero bits.
+    ///
+    ///    ip = IPAddress("172.16.10.1/24")
+    ///
+    ///    ip.octets()
+    ///      ///  [172, 16, 10, 1]
+    ///
+    pub fn octets(&self) -> Vec<u8> {
+        let len = (self.ip_bits.bits + 7) / 8;
+        let bytes = self.host_address.to_bytes_be();
+        let mut ret = vec![0u8; len - bytes.len()];
+        ret.extend(bytes);
+        ret
+    }
+
+    ///  Creates an address from octets in network byte order,
+    ///  4 octets are IPv4 and 16 octets are IPv6.
+    ///
+    ///    IPAddress::from_octets(&[10, 0, 0, 1], 8).to_string
+    ///      ///  "10.0.0.1/8"
+    ///
+    pub fn from_octets(octets: &[u8], prefix: usize) -> Result<IPAddress, String> {
+        match octets.len() {
+            4 => ::ipv4::from_octets([octets[0], octets[1], octets[2], octets[3]], prefix),
+            16 => {
+                let mut bytes = [0u8; 16];
+                bytes.copy_from_slice(octets);
+                ::ipv6::from_octets(bytes, prefix)
+            }
+            len => Err(format!("IP address needs 4 or 16 octets, got {}", len)),
+        }
+    }
+
+    ///  Creates an IPv4 or IPv6 address from a u128, the
+    ///  counterpart of to_u128. Custom families have no u128
+    ///  constructor and return an error.
+    ///
+    ///    IPAddress::from_u128(IpVersion::V4, 0x0a000001, 8).to_string
+    ///      ///  "10.0.0.1/8"
+    ///
+    pub fn from_u128(version: IpVersion, addr: u128, prefix: usize) -> Result<IPAddress, String> {
+        match version {
+            IpVersion::V4 => {
+                if addr > u32::MAX as u128 {
+                    return Err(format!("{:x} is not an IPv4 address", addr));
+                }
+                ::ipv4::from_u32(addr as u32, prefix)
+            }
+            IpVersion::V6 => ::ipv6::from_u128(addr, prefix),
+            _ => Err(format!("{} addresses can not be built from a u128", version.name())),
+        }
+    }
+
+    ///  Returns bit +i+ of the address, counted from the most
+    ///  significant bit like a prefix length.
+    ///
+    ///    ip = IPAddress("128.0.0.1")
+    ///
+    ///    ip.bit(0)
+    ///      ///  true
+    ///    ip.bit(31)
+    ///      ///  true
+    ///
+    ///  Panics if +i+ is not below the width of the family.
+    pub fn bit(&self, i: usize) -> bool {
+        let shift = self.bit_shift(i);
+        !((self.host_address.clone() >> shift) & BigUint::one()).is_zero()
+    }
+
+    ///  Returns a copy of the address with bit +i+, counted from
+    ///  the most significant bit, set to +value+.
+    ///
+    ///    IPAddress("10.0.0.0/8").set_bit(31, true).to_string
+    ///      ///  "10.0.0.1/8"
+    ///
+    pub fn set_bit(&self, i: usize, value: bool) -> IPAddress {
+        let bit = BigUint::one() << self.bit_shift(i);
+        let addr = if value {
+            &self.host_address | &bit
+        } else {
+            &self.host_address & &(self.max_host_address() ^ bit)
+        };
+        self.rebuild(&addr, &self.prefix)
+    }
+
+    fn bit_shift(&self, i: usize) -> usize {
+        assert!(i < self.ip_bits.bits, "bit {} out of range for {} bits", i, self.ip_bits.bits);
+        self.ip_bits.bits - 1 - i
+    }
+
+    ///  Returns the number of leading zero bits within the
+    ///  width of the family.
+    ///
+    ///    IPAddress("0.0.255.255").leading_zeros()
+    ///      ///  16
+    ///
+    pub fn leading_zeros(&self) -> usize {
+        self.ip_bits.bits - self.host_address.bits()
+    }
+
+    ///  Returns the number of leading bits +a+ and +b+ have in
+    ///  common, addresses of different families share none.
+    ///
+    ///    IPAddress::common_prefix_len(IPAddress("10.0.0.1"), IPAddress("10.0.0.6"))
+    ///      ///  29
+    ///
+    pub fn common_prefix_len(a: &IPAddress, b: &IPAddress) -> usize {
+        if a.ip_bits.version != b.ip_bits.version {
+            return 0;
+        }
+        (a ^ b).leading_zeros()
+    }
+
     pub fn netmask(&self) -> IPAddress {
         self.from(&self.prefix.netmask(), &self.prefix)
     }
@@ -904,6 +1340,155 @@ impl IPAddress {
         return IPAddress::aggregate(&[self.clone(), other.clone()].to_vec());
     }
 
+    fn offset(n: u128) -> BigUint {
+        BigUint::from_bytes_be(&n.to_be_bytes())
+    }
+
+    fn max_host_address(&self) -> BigUint {
+        (BigUint::one() << self.ip_bits.bits) - BigUint::one()
+    }
+
+    ///  Returns the address +n+ above, None if that would be
+    ///  past the top of the address space. The prefix is kept.
+    ///
+    ///    ip = IPAddress("10.0.0.1/24")
+    ///
+    ///    ip.checked_add(5).to_string
+    ///      ///  "10.0.0.6/24"
+    ///    IPAddress("255.255.255.255").checked_add(1)
+    ///      ///  None
+    ///
+    pub fn checked_add(&self, n: u128) -> Option<IPAddress> {
+        let addr = self.host_address.clone() + IPAddress::offset(n);
+        if addr > self.max_host_address() {
+            return None;
+        }
+        Some(self.rebuild(&addr, &self.prefix))
+    }
+
+    ///  Returns the address +n+ below, None if that would be
+    ///  below the bottom of the address space.
+    pub fn checked_sub(&self, n: u128) -> Option<IPAddress> {
+        let n = IPAddress::offset(n);
+        if n > self.host_address {
+            return None;
+        }
+        Some(self.rebuild(&(self.host_address.clone() - n), &self.prefix))
+    }
+
+    ///  Like checked_add, but wraps around at the top of the
+    ///  address space of the family.
+    ///
+    ///    IPAddress("255.255.255.255/8").wrapping_add(2).to_string
+    ///      ///  "0.0.0.1/8"
+    ///
+    pub fn wrapping_add(&self, n: u128) -> IPAddress {
+        let size = BigUint::one() << self.ip_bits.bits;
+        let addr = (self.host_address.clone() + IPAddress::offset(n) % &size) % &size;
+        self.rebuild(&addr, &self.prefix)
+    }
+
+    ///  Like checked_sub, but wraps around at the bottom of the
+    ///  address space of the family.
+    pub fn wrapping_sub(&self, n: u128) -> IPAddress {
+        let size = BigUint::one() << self.ip_bits.bits;
+        let addr = (self.host_address.clone() + &size - IPAddress::offset(n) % &size) % &size;
+        self.rebuild(&addr, &self.prefix)
+    }
+
+    ///  Like checked_add, but stops at the highest address of
+    ///  the family.
+    pub fn saturating_add(&self, n: u128) -> IPAddress {
+        self.checked_add(n).unwrap_or_else(|| self.rebuild(&self.max_host_address(), &self.prefix))
+    }
+
+    ///  Like checked_sub, but stops at the lowest address of
+    ///  the family.
+    pub fn saturating_sub(&self, n: u128) -> IPAddress {
+        self.checked_sub(n).unwrap_or_else(|| self.rebuild(&BigUint::zero(), &self.prefix))
+    }
+
+    ///  Like checked_add, but None if the result leaves the
+    ///  enclosing network.
+    ///
+    ///    ip = IPAddress("10.0.0.250/24")
+    ///
+    ///    ip.checked_add_in_network(5).to_string
+    ///      ///  "10.0.0.255/24"
+    ///    ip.checked_add_in_network(6)
+    ///      ///  None
+    ///
+    pub fn checked_add_in_network(&self, n: u128) -> Option<IPAddress> {
+        self.checked_add(n).filter(|i| i.host_address <= self.broadcast().host_address)
+    }
+
+    ///  Like checked_sub, but None if the result leaves the
+    ///  enclosing network.
+    pub fn checked_sub_in_network(&self, n: u128) -> Option<IPAddress> {
+        self.checked_sub(n).filter(|i| i.host_address >= self.network().host_address)
+    }
+
+    ///  Returns the following address, None at the top of
+    ///  the address space.
+    ///
+    ///    IPAddress("10.0.0.255/24").next().to_string
+    ///      ///  "10.0.1.0/24"
+    ///
+    pub fn next(&self) -> Option<IPAddress> {
+        self.checked_add(1)
+    }
+
+    ///  Returns the preceding address, None at the bottom of
+    ///  the address space.
+    pub fn prev(&self) -> Option<IPAddress> {
+        self.checked_sub(1)
+    }
+
+    ///  Returns the +n+th usable host of the network counting
+    ///  from zero, None if the network has fewer hosts. Usable
+    ///  hosts follow HostPolicy::Classic.
+    ///
+    ///    ip = IPAddress("192.168.1.77/24")
+    ///
+    ///    ip.nth_host(0).to_string
+    ///      ///  "192.168.1.1/24"
+    ///    ip.nth_host(253).to_string
+    ///      ///  "192.168.1.254/24"
+    ///    ip.nth_host(254)
+    ///      ///  None
+    ///
+    pub fn nth_host(&self, n: u128) -> Option<IPAddress> {
+        self.nth_host_with(n, &HostPolicy::Classic)
+    }
+
+    pub fn nth_host_with(&self, n: u128, policy: &HostPolicy) -> Option<IPAddress> {
+        let first = policy.first(self)?;
+        first.checked_add(n).filter(|_| IPAddress::offset(n) < policy.host_count(self))
+    }
+
+    ///  Returns the signed distance from the object to +other+,
+    ///  positive if +other+ is the higher address. Unlike sub,
+    ///  which returns the absolute distance. Addresses of
+    ///  different families have no distance, that is an error.
+    ///
+    ///    ip1 = IPAddress("10.0.0.10")
+    ///    ip2 = IPAddress("10.0.0.1")
+    ///
+    ///    ip1.distance(ip2)
+    ///      ///  Ok(-9)
+    ///
+    pub fn distance(&self, other: &IPAddress) -> Result<BigInt, String> {
+        if self.ip_bits.version != other.ip_bits.version {
+            return Err(format!("{} and {} are of different families", self.to_string(), other.to_string()));
+        }
+        let sign = if other.host_address < self.host_address {
+            Sign::Minus
+        } else {
+            Sign::Plus
+        };
+        Ok(BigInt::from_biguint(sign, self.sub(other)))
+    }
+
     pub fn to_s_vec(vec: &Vec<IPAddress>) -> Vec<String> {
         let mut ret : Vec<String> = Vec::new();
         for i in vec {
@@ -951,8 +1536,28 @@ impl IPAddress {
     ///    ip.first.to_s
     ///      ///  "192.168.100.1"
     ///
+    ///  The first host follows HostPolicy::Classic. If the
+    ///  network has no usable host, like a /31 or /32, the
+    ///  network address is returned, up to version 0.1.1 it
+    ///  was the address behind it, which lies outside of a /32.
+    ///
+    ///    IPAddress("10.0.0.5/32").first.to_s
+    ///      ///  "10.0.0.5"
+    ///
     pub fn first(&self) -> IPAddress {
-        return self.from(&self.network().host_address.add(&self.ip_bits.host_ofs), &self.prefix);
+        self.first_with(&HostPolicy::Classic).unwrap_or_else(|| self.network())
+    }
+
+    ///  Returns the first usable host of the network according
+    ///  to the given HostPolicy, None if there is none.
+    ///
+    ///    ip = IPAddress("10.0.0.0/31")
+    ///
+    ///    ip.first_with(&HostPolicy::Rfc3021).to_s
+    ///      ///  "10.0.0.0"
+    ///
+    pub fn first_with(&self, policy: &HostPolicy) -> Option<IPAddress> {
+        policy.first(self)
     }
 
     ///  Like its sibling method IPv4/// first, this method
@@ -975,9 +1580,48 @@ impl IPAddress {
     ///    ip.last.to_s
     ///      ///  "192.168.100.254"
     ///
+    ///  The last host follows HostPolicy::Classic. If the
+    ///  network has no usable host, like a /31 or /32, the
+    ///  broadcast address is returned, up to version 0.1.1 it
+    ///  was the address in front of it, which lies outside of
+    ///  a /32.
+    ///
+    ///    IPAddress("10.0.0.5/32").last.to_s
+    ///      ///  "10.0.0.5"
+    ///
     #[allow(dead_code)]
     pub fn last(&self) -> IPAddress {
-        return self.from(&self.broadcast().host_address.sub(&self.ip_bits.host_ofs), &self.prefix);
+        self.last_with(&HostPolicy::Classic).unwrap_or_else(|| self.broadcast())
+    }
+
+    ///  Returns the last usable host of the network according
+    ///  to the given HostPolicy, None if there is none.
+    ///
+    ///    ip = IPAddress("10.0.0.0/24")
+    ///
+    ///    ip.last_with(&HostPolicy::Reserved { head: 4, tail: 1 }).to_s
+    ///      ///  "10.0.0.254"
+    ///
+    pub fn last_with(&self, policy: &HostPolicy) -> Option<IPAddress> {
+        policy.last(self)
+    }
+
+    ///  Returns the number of usable hosts in the network
+    ///  following HostPolicy::Classic. In contrast to size
+    ///  the network and broadcast addresses of IPv4 are not
+    ///  counted.
+    ///
+    ///    ip = IPAddress("10.0.0.1/29")
+    ///
+    ///    ip.host_count
+    ///      ///  6
+    ///
+    pub fn host_count(&self) -> BigUint {
+        self.host_count_with(&HostPolicy::Classic)
+    }
+
+    pub fn host_count_with(&self, policy: &HostPolicy) -> BigUint {
+        policy.host_count(self)
     }
 
     ///  Iterates over all the hosts IP addresses for the given
@@ -997,10 +1641,27 @@ impl IPAddress {
     ///
     #[allow(dead_code)]
     pub fn each_host<F>(&self, func: F) where F : Fn(&IPAddress) {
-        let mut i = self.first().host_address;
-        while i <= self.last().host_address {
-            func(&mut self.from(&i, &self.prefix));
-            i = i.add(BigUint::one());
+        self.each_host_with(&HostPolicy::Classic, func);
+    }
+
+    ///  Iterates over all the hosts of the network which are
+    ///  usable according to the given HostPolicy.
+    ///
+    ///    ip = IPAddress("10.0.0.0/31")
+    ///
+    ///    ip.each_host_with(&HostPolicy::Rfc3021) do |i|
+    ///      p i.to_s
+    ///    end
+    ///      ///  "10.0.0.0"
+    ///      ///  "10.0.0.1"
+    ///
+    pub fn each_host_with<F>(&self, policy: &HostPolicy, func: F) where F : Fn(&IPAddress) {
+        if let (Some(first), Some(last)) = (policy.first(self), policy.last(self)) {
+            let mut i = first.host_address;
+            while i <= last.host_address {
+                func(&self.from(&i, &self.prefix));
+                i += BigUint::one();
+            }
         }
     }
 
@@ -1079,8 +1740,7 @@ impl IPAddress {
     }
     #[allow(dead_code)]
     pub fn is_same_kind(&self, oth: &IPAddress) -> bool {
-        return self.is_ipv4() == oth.is_ipv4() &&
-        self.is_ipv6() == oth.is_ipv6();
+        self.ip_bits.version == oth.ip_bits.version
     }
 
     ///  Checks whether a subnet includes the given IP address.
@@ -1106,6 +1766,28 @@ impl IPAddress {
         return ret
     }
 
+    ///  Returns the network both objects have in common, which
+    ///  is the smaller of the two networks, None if they do not
+    ///  overlap.
+    ///
+    ///    ip = IPAddress("192.168.10.0/24")
+    ///
+    ///    ip.intersection(IPAddress("192.168.10.64/26")).to_string
+    ///      ///  "192.168.10.64/26"
+    ///
+    ///    ip.intersection(IPAddress("192.168.11.0/24"))
+    ///      ///  None
+    ///
+    pub fn intersection(&self, oth: &IPAddress) -> Option<IPAddress> {
+        if self.includes(oth) {
+            Some(oth.network())
+        } else if oth.includes(self) {
+            Some(self.network())
+        } else {
+            None
+        }
+    }
+
     ///  Checks whether a subnet includes all the
     ///  given IPv4 objects.
     ///
@@ -1283,6 +1965,72 @@ impl IPAddress {
         return Ok(ret);
     }
 
+    ///  Returns the smallest list of networks which covers
+    ///  exactly the addresses from +first+ to +last+, both
+    ///  included. The networks inherit the family of the
+    ///  object.
+    ///
+    ///    ip = IPAddress("10.0.0.0/8")
+    ///
+    ///    ip.range_to_networks(10.0.0.1, 10.0.0.6).map(&:to_string)
+    ///      ///  ["10.0.0.1/32", "10.0.0.2/31", "10.0.0.4/31",
+    ///           "10.0.0.6/32"]
+    ///
+    pub fn range_to_networks(&self, first: &BigUint, last: &BigUint) -> Vec<IPAddress> {
+        let mut ret = Vec::new();
+        let bits = self.ip_bits.bits;
+        let mut cur = first.clone();
+        while cur <= *last {
+            let mut host_prefix = 0;
+            while host_prefix < bits && ((cur.clone() >> host_prefix) & BigUint::one()).is_zero() {
+                host_prefix += 1;
+            }
+            while host_prefix > 0 &&
+                  cur.clone() + (BigUint::one() << host_prefix) - BigUint::one() > *last {
+                host_prefix -= 1;
+            }
+            let prefix = self.prefix.from(bits - host_prefix).unwrap();
+            ret.push(self.from(&cur, &prefix));
+            cur += BigUint::one() << host_prefix;
+        }
+        ret
+    }
+
+    ///  Returns the networks which are left over if all the
+    ///  given networks are removed from the network of the
+    ///  object. Networks of the other family are ignored.
+    ///
+    ///    ip = IPAddress("10.0.0.0/24")
+    ///
+    ///    ip.exclude([IPAddress("10.0.0.64/26")]).map(&:to_string)
+    ///      ///  ["10.0.0.0/26", "10.0.0.128/25"]
+    ///
+    pub fn exclude(&self, others: &[IPAddress]) -> Vec<IPAddress> {
+        let network = self.network();
+        let broadcast = self.broadcast().host_address;
+        let mut ranges = others.iter()
+            .filter(|i| self.is_same_kind(i))
+            .map(|i| (i.network().host_address, i.broadcast().host_address))
+            .filter(|r| r.0 <= broadcast && r.1 >= network.host_address)
+            .collect::<Vec<_>>();
+        ranges.sort();
+        let mut ret = Vec::new();
+        let mut cur = network.host_address.clone();
+        for (from, to) in ranges {
+            if from > cur {
+                ret.extend(network.range_to_networks(&cur, &(from - BigUint::one())));
+            }
+            if to >= cur {
+                if to >= broadcast {
+                    return ret;
+                }
+                cur = to + BigUint::one();
+            }
+        }
+        ret.extend(network.range_to_networks(&cur, &broadcast));
+        ret
+    }
+
 
 
     ///  Return the ip address in a format compatible
@@ -0,0 +1,346 @@
+//!  IP address management on top of IPAddress.
+//!
+//!  An Allocator manages a list of parent pools. Within each
+//!  pool networks can be reserved (never handed out) or
+//!  allocated. Free space is always expressed as the list of
+//!  aligned networks not covered by any reservation or
+//!  allocation.
+//!
+//!    let mut ipam = Allocator::new();
+//!    ipam.add_pool(&IPAddress::parse("10.0.0.0/16").unwrap())
+//!    ipam.reserve(&IPAddress::parse("10.0.0.0/24").unwrap())
+//!
+//!    ipam.allocate(IpVersion::V4, 26, Policy::FirstFit).to_string()
+//!      ///  "10.0.1.0/26"
+
+use ip_bits::IpVersion;
+use ipaddress::IPAddress;
+use num::bigint::BigUint;
+use num_traits::identities::Zero;
+use num_traits::cast::ToPrimitive;
+
+///  Strategy used to pick the free block an allocation is cut from.
+///
+///  * FirstFit takes the lowest free block large enough.
+///  * BestFit takes the smallest free block large enough, which
+///    keeps the large blocks intact for later requests.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    FirstFit,
+    BestFit,
+}
+
+#[derive(Debug, Clone)]
+pub struct Pool {
+    pub network: IPAddress,
+    pub allocated: Vec<IPAddress>,
+    pub reserved: Vec<IPAddress>,
+}
+
+///  Address counts of a pool. Reserved addresses are counted
+///  as used, the same as allocated ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Usage {
+    pub total: BigUint,
+    pub allocated: BigUint,
+    pub reserved: BigUint,
+    pub free: BigUint,
+}
+
+impl Usage {
+    ///  Returns the used part of the pool in percent
+    ///
+    ///    usage.utilization()
+    ///      ///  37.5
+    ///
+    pub fn utilization(&self) -> f64 {
+        let used = (self.allocated.clone() + self.reserved.clone()).to_f64().unwrap();
+        used * 100.0 / self.total.to_f64().unwrap()
+    }
+}
+
+fn is_same_network(a: &IPAddress, b: &IPAddress) -> bool {
+    a.is_same_kind(b) && a.prefix.num == b.prefix.num && a.host_address == b.host_address
+}
+
+fn overlaps(a: &IPAddress, b: &IPAddress) -> bool {
+    a.includes(b) || b.includes(a)
+}
+
+fn check_aligned(net: &IPAddress) -> Result<(), String> {
+    if net.host_address != net.network().host_address {
+        return Err(format!("{} is not a network address", net.to_string()));
+    }
+    Ok(())
+}
+
+fn sum_sizes(nets: &[IPAddress]) -> BigUint {
+    nets.iter().fold(BigUint::zero(), |acc, i| acc + i.size())
+}
+
+impl Pool {
+    fn new(network: IPAddress) -> Pool {
+        Pool {
+            network,
+            allocated: Vec::new(),
+            reserved: Vec::new(),
+        }
+    }
+
+    fn used(&self) -> Vec<IPAddress> {
+        let mut ret = self.allocated.clone();
+        ret.extend(self.reserved.iter().cloned());
+        ret.sort();
+        ret
+    }
+
+    fn check_free(&self, net: &IPAddress) -> Result<(), String> {
+        if !self.network.includes(net) {
+            return Err(format!("{} is not part of pool {}", net.to_string(), self.network.to_string()));
+        }
+        for used in self.allocated.iter().chain(self.reserved.iter()) {
+            if overlaps(used, net) {
+                return Err(format!("{} overlaps with {}", net.to_string(), used.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    ///  Returns the free space of the pool as a sorted list
+    ///  of the largest aligned networks
+    pub fn free_blocks(&self) -> Vec<IPAddress> {
+        self.network.exclude(&self.used())
+    }
+
+    pub fn usage(&self) -> Usage {
+        let total = self.network.size();
+        let allocated = sum_sizes(&self.allocated);
+        let reserved = sum_sizes(&self.reserved);
+        let free = total.clone() - allocated.clone() - reserved.clone();
+        Usage {
+            total,
+            allocated,
+            reserved,
+            free,
+        }
+    }
+
+    fn allocate(&mut self, prefix: usize, policy: Policy) -> Result<IPAddress, String> {
+        if prefix < self.network.prefix.num || self.network.ip_bits.bits < prefix {
+            return Err(format!("Prefix {} does not fit into pool {}", prefix, self.network.to_string()));
+        }
+        let fitting = self.free_blocks().into_iter().filter(|i| i.prefix.num <= prefix);
+        let block = match policy {
+            Policy::FirstFit => fitting.min_by(|a, b| a.host_address.cmp(&b.host_address)),
+            Policy::BestFit => fitting.min_by(|a, b| {
+                b.prefix.num.cmp(&a.prefix.num).then(a.host_address.cmp(&b.host_address))
+            }),
+        };
+        match block {
+            Some(block) => {
+                let ret = block.change_prefix(prefix).unwrap();
+                self.allocated.push(ret.clone());
+                self.allocated.sort();
+                Ok(ret)
+            }
+            None => Err(format!("No free /{} left in pool {}", prefix, self.network.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Allocator {
+    pools: Vec<Pool>,
+}
+
+impl Allocator {
+    pub fn new() -> Allocator {
+        Allocator { pools: Vec::new() }
+    }
+
+    pub fn pools(&self) -> &[Pool] {
+        &self.pools
+    }
+
+    pub fn pool(&self, network: &IPAddress) -> Result<&Pool, String> {
+        match self.pools.iter().find(|i| is_same_network(&i.network, network)) {
+            Some(pool) => Ok(pool),
+            None => Err(format!("Unknown pool {}", network.to_string())),
+        }
+    }
+
+    fn pool_mut(&mut self, network: &IPAddress) -> Result<&mut Pool, String> {
+        match self.pools.iter_mut().find(|i| is_same_network(&i.network, network)) {
+            Some(pool) => Ok(pool),
+            None => Err(format!("Unknown pool {}", network.to_string())),
+        }
+    }
+
+    fn enclosing_pool_mut(&mut self, net: &IPAddress) -> Result<&mut Pool, String> {
+        match self.pools.iter_mut().find(|i| i.network.includes(net)) {
+            Some(pool) => Ok(pool),
+            None => Err(format!("{} is not part of any pool", net.to_string())),
+        }
+    }
+
+    ///  Adds a parent pool. The pool is stored as the network
+    ///  of the given address and must not overlap with any
+    ///  existing pool.
+    pub fn add_pool(&mut self, network: &IPAddress) -> Result<(), String> {
+        let network = network.network();
+        for pool in self.pools.iter() {
+            if overlaps(&pool.network, &network) {
+                return Err(format!("Pool {} overlaps with pool {}",
+                                   network.to_string(), pool.network.to_string()));
+            }
+        }
+        self.pools.push(Pool::new(network));
+        Ok(())
+    }
+
+    ///  Removes an empty pool
+    pub fn remove_pool(&mut self, network: &IPAddress) -> Result<(), String> {
+        let idx = match self.pools.iter().position(|i| is_same_network(&i.network, network)) {
+            Some(idx) => idx,
+            None => return Err(format!("Unknown pool {}", network.to_string())),
+        };
+        if !self.pools[idx].allocated.is_empty() || !self.pools[idx].reserved.is_empty() {
+            return Err(format!("Pool {} is still in use", network.to_string()));
+        }
+        self.pools.remove(idx);
+        Ok(())
+    }
+
+    ///  Reserves a network inside a pool, so that it is never
+    ///  handed out by allocate.
+    pub fn reserve(&mut self, net: &IPAddress) -> Result<(), String> {
+        check_aligned(net)?;
+        let pool = self.enclosing_pool_mut(net)?;
+        pool.check_free(net)?;
+        pool.reserved.push(net.clone());
+        pool.reserved.sort();
+        Ok(())
+    }
+
+    pub fn unreserve(&mut self, net: &IPAddress) -> Result<(), String> {
+        let pool = self.enclosing_pool_mut(net)?;
+        match pool.reserved.iter().position(|i| is_same_network(i, net)) {
+            Some(idx) => {
+                pool.reserved.remove(idx);
+                Ok(())
+            }
+            None => Err(format!("{} is not reserved", net.to_string())),
+        }
+    }
+
+    ///  Allocates the next free network with the given prefix
+    ///  from the first pool of family +version+ which has room
+    ///  for it.
+    ///
+    ///    ipam.allocate(IpVersion::V4, 24, Policy::BestFit)
+    ///
+    pub fn allocate(&mut self, version: IpVersion, prefix: usize, policy: Policy) -> Result<IPAddress, String> {
+        for pool in self.pools.iter_mut() {
+            if pool.network.ip_bits.version != version
+                || prefix < pool.network.prefix.num
+                || pool.network.ip_bits.bits < prefix
+            {
+                continue;
+            }
+            if let Ok(ret) = pool.allocate(prefix, policy) {
+                return Ok(ret);
+            }
+        }
+        Err(format!("No free /{} left in any {:?} pool", prefix, version))
+    }
+
+    ///  Allocates the next free network with the given prefix
+    ///  from a specific pool.
+    pub fn allocate_from(&mut self, pool: &IPAddress, prefix: usize, policy: Policy)
+                         -> Result<IPAddress, String> {
+        self.pool_mut(pool)?.allocate(prefix, policy)
+    }
+
+    ///  Allocates exactly the given network
+    pub fn allocate_network(&mut self, net: &IPAddress) -> Result<(), String> {
+        check_aligned(net)?;
+        let pool = self.enclosing_pool_mut(net)?;
+        pool.check_free(net)?;
+        pool.allocated.push(net.clone());
+        pool.allocated.sort();
+        Ok(())
+    }
+
+    ///  Returns a previously allocated network to its pool
+    pub fn free(&mut self, net: &IPAddress) -> Result<(), String> {
+        let pool = self.enclosing_pool_mut(net)?;
+        match pool.allocated.iter().position(|i| is_same_network(i, net)) {
+            Some(idx) => {
+                pool.allocated.remove(idx);
+                Ok(())
+            }
+            None => Err(format!("{} is not allocated", net.to_string())),
+        }
+    }
+
+    pub fn usage(&self, pool: &IPAddress) -> Result<Usage, String> {
+        Ok(self.pool(pool)?.usage())
+    }
+
+    pub fn free_blocks(&self, pool: &IPAddress) -> Result<Vec<IPAddress>, String> {
+        Ok(self.pool(pool)?.free_blocks())
+    }
+
+    ///  Serializes the state into a line based text format
+    ///
+    ///    pool 10.0.0.0/16
+    ///    reserved 10.0.0.0/24
+    ///    allocated 10.0.1.0/26
+    ///
+    pub fn dump(&self) -> String {
+        let mut ret = String::new();
+        for pool in self.pools.iter() {
+            ret.push_str(&format!("pool {}\n", pool.network.to_string()));
+            for net in pool.reserved.iter() {
+                ret.push_str(&format!("reserved {}\n", net.to_string()));
+            }
+            for net in pool.allocated.iter() {
+                ret.push_str(&format!("allocated {}\n", net.to_string()));
+            }
+        }
+        ret
+    }
+
+    ///  Restores an allocator from the output of dump. Empty
+    ///  lines and lines starting with # are ignored.
+    pub fn load(state: &str) -> Result<Allocator, String> {
+        let mut ret = Allocator::new();
+        for (lineno, line) in state.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let kind = words.next().unwrap();
+            let net = match (words.next(), words.next()) {
+                (Some(net), None) => net,
+                _ => return Err(format!("line {}: malformed entry {}", lineno + 1, line)),
+            };
+            let net = match IPAddress::parse(net) {
+                Ok(net) => net,
+                Err(err) => return Err(format!("line {}: {}", lineno + 1, err)),
+            };
+            let res = match kind {
+                "pool" => ret.add_pool(&net),
+                "reserved" => ret.reserve(&net),
+                "allocated" => ret.allocate_network(&net),
+                _ => Err(format!("unknown entry {}", kind)),
+            };
+            if let Err(err) = res {
+                return Err(format!("line {}: {}", lineno + 1, err));
+            }
+        }
+        Ok(ret)
+    }
+}
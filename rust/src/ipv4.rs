@@ -4,6 +4,7 @@ use num::bigint::BigUint;
 // use ip_bits::IpBits;
 use ipaddress::IPAddress;
 use prefix32;
+use literal::{IPV4_LOOPBACK, IPV4_PRIVATE};
 // use num_integer::Integer;
 
 
@@ -83,6 +84,16 @@ pub fn from_u32(addr: u32, prefix: usize) -> Result<IPAddress, String> {
     });
 }
 
+///  Creates an IPv4 address from its four octets in network
+///  byte order.
+///
+///    ipv4::from_octets([172, 16, 10, 1], 24).unwrap().to_string()
+///      ///  "172.16.10.1/24"
+///
+pub fn from_octets(octets: [u8; 4], prefix: usize) -> Result<IPAddress, String> {
+    from_u32(u32::from_be_bytes(octets), prefix)
+}
+
 pub fn new<S: Into<String>>(_str: S) -> Result<IPAddress, String> {
     let str = _str.into();
     let (ip, netmask) = IPAddress::split_at_slash(&str);
@@ -90,9 +101,9 @@ pub fn new<S: Into<String>>(_str: S) -> Result<IPAddress, String> {
         return Err(format!("Invalid IP {}", str));
     }
     let mut ip_prefix_num = Ok(32);
-    if netmask.is_some() {
+    if let Some(netmask) = netmask {
         //  netmask is defined
-        ip_prefix_num = IPAddress::parse_netmask_to_prefix(netmask.unwrap());
+        ip_prefix_num = IPAddress::parse_netmask_to_prefix_for(&netmask, 32);
         if ip_prefix_num.is_err() {
             return Err(ip_prefix_num.unwrap_err());
         }
@@ -118,15 +129,11 @@ pub fn new<S: Into<String>>(_str: S) -> Result<IPAddress, String> {
 }
 
 fn ipv4_is_private(my: &IPAddress) -> bool {
-    return [IPAddress::parse("10.0.0.0/8").unwrap(),
-     IPAddress::parse("172.16.0.0/12").unwrap(),
-     IPAddress::parse("192.168.0.0/16").unwrap()]
-     .iter().find(|i| i.includes(my)).is_some();
+    IPV4_PRIVATE.iter().any(|i| i.includes(my))
 }
 
 fn ipv4_is_loopback(my: &IPAddress) -> bool {
-    return IPAddress::parse("127.0.0.0/8")
-        .unwrap().includes(my);
+    IPV4_LOOPBACK.includes(my)
 }
 
 pub fn to_ipv6(ia: &IPAddress) -> IPAddress {
@@ -838,6 +845,38 @@ pub fn is_class_c(my: &IPAddress) -> bool {
         my.host_address < BigUint::from_u32(0xe0000000).unwrap();
 }
 
+//  Checks whether the ip address belongs to a
+//  RFC 1112 CLASS D (multicast) network, no matter
+//  what the subnet mask is.
+//
+//  Example:
+//
+//    ip = IPAddress("224.0.0.1/24")
+//
+//    ip.d?
+//      // => true
+//
+pub fn is_class_d(my: &IPAddress) -> bool {
+    my.is_ipv4() &&
+        BigUint::from_u32(0xe0000000).unwrap() <= my.host_address &&
+        my.host_address < BigUint::from_u32(0xf0000000).unwrap()
+}
+
+//  Checks whether the ip address belongs to the
+//  reserved CLASS E network, no matter what the
+//  subnet mask is.
+//
+//  Example:
+//
+//    ip = IPAddress("240.0.0.1/24")
+//
+//    ip.e?
+//      // => true
+//
+pub fn is_class_e(my: &IPAddress) -> bool {
+    my.is_ipv4() && BigUint::from_u32(0xf0000000).unwrap() <= my.host_address
+}
+
 //  Return the ip address in a format compatible
 //  with the IPv6 Mapped IPv4 addresses
 //
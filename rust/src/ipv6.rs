@@ -2,7 +2,6 @@
 use ipaddress::IPAddress;
 use core::result::Result;
 use num::bigint::BigUint;
-use core::str::FromStr;
 use num_traits::One;
 use num_traits::Num;
 use core::ops::Rem;
@@ -12,6 +11,7 @@ use num_traits::Zero;
 use num_traits::FromPrimitive;
 use num_traits::ToPrimitive;
 use prefix128;
+use literal::IPV6_PRIVATE;
 use ipv4;
 
 ///  =Name
@@ -91,15 +91,12 @@ pub fn enhance_if_mapped(mut ip: IPAddress) -> Result<IPAddress, String> {
         if num == BigUint::zero() {
             return Ok(ip);
         }
-        println!("ip:{},{:x}", ip.to_string(), num);
         let ipv4_bits = ::ip_bits::v4();
         if ipv4_bits.bits < ip.prefix.host_prefix() {
-            println!("enhance_if_mapped-2:{}:{}", ip.to_string(), ip.prefix.host_prefix());
             return Err(format!("enhance_if_mapped prefix not ipv4 compatible {}", ip.prefix.host_prefix()));
         }
         let mapped = ipv4::from_u32(num.to_u32().unwrap(), ipv4_bits.bits-ip.prefix.host_prefix());
         if mapped.is_err() {
-            println!("enhance_if_mapped-3");
             return mapped;
         }
         // println!("real mapped!!!!!={}", mapped.clone().unwrap().to_string());
@@ -125,6 +122,21 @@ pub fn from_int(adr: BigUint, prefix: usize) -> Result<IPAddress, String> {
 }
 
 
+///  Creates an IPv6 address from a u128, like from_int.
+///
+///    ipv6::from_u128(0x20010db8 << 96, 32).unwrap().to_string()
+///      ///  "2001:db8::/32"
+///
+pub fn from_u128(adr: u128, prefix: usize) -> Result<IPAddress, String> {
+    from_int(BigUint::from_bytes_be(&adr.to_be_bytes()), prefix)
+}
+
+///  Creates an IPv6 address from its sixteen octets in network
+///  byte order.
+pub fn from_octets(octets: [u8; 16], prefix: usize) -> Result<IPAddress, String> {
+    from_int(BigUint::from_bytes_be(&octets), prefix)
+}
+
 ///  Creates a new IPv6 address object.
 ///
 ///  An IPv6 address can be expressed in any of the following forms:
@@ -151,11 +163,8 @@ pub fn new<S: Into<String>>(_str: S) -> Result<IPAddress, String> {
         let mut netmask = 128;
         if o_netmask.is_some() {
             let network = o_netmask.unwrap();
-            let num_mask = u8::from_str(&network);
-            if num_mask.is_err() {
-                return Err(format!("Invalid Netmask {}", str));
-            }
-            netmask = network.parse::<usize>().unwrap();
+            netmask = IPAddress::parse_netmask_to_prefix_for(&network, 128)
+                .map_err(|e| format!("Invalid Netmask {}: {}", str, e))?;
         }
         let prefix = ::prefix128::new(netmask);
         if prefix.is_err() {
@@ -185,5 +194,5 @@ pub fn ipv6_is_loopback(my: &IPAddress) -> bool {
 
 
 pub fn ipv6_is_private(my: &IPAddress) -> bool {
-    return IPAddress::parse("fd00::/8").unwrap().includes(my);
+    IPV6_PRIVATE.includes(my)
 }
@@ -90,6 +90,12 @@ use num_traits::Zero;
 pub fn new<S: Into<String>>(_str: S) -> Result<IPAddress, String> {
     let str = _str.into();
     let (ip, o_netmask) = IPAddress::split_at_slash(&str);
+    if let (false, Some(netmask)) = (str.contains('/'), o_netmask.as_ref()) {
+        // a netmask after a space belongs to the IPv6 address,
+        // only /24 means the prefix of the IPv4 part
+        let num = IPAddress::parse_netmask_to_prefix_for(netmask, 128)?;
+        return ::ipv6::from_int(new(ip.clone())?.host_address, num);
+    }
     let split_colon = ip.split(":").collect::<Vec<&str>>();
     if split_colon.len() <= 1 {
         // println!("---1");
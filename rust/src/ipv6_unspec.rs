@@ -51,5 +51,5 @@ use ipaddress::IPAddress;
 ///
 #[allow(dead_code)]
 pub fn new() -> IPAddress {
-    return IPAddress::parse("::").unwrap();
+    ip!("::")
 }
@@ -1,8 +1,29 @@
+//!  Without the default std feature the crate is no_std, but
+//!  it is not the same crate: IPAddress holds a num 0.1
+//!  BigUint, which needs std, so IPAddress, Prefix and every
+//!  module built on them are left out. What remains is
+//!  literal::IpLiteral, a separate u128 address type which
+//!  parses, formats, subnets and classifies without a heap,
+//!  and ip_bits::IpVersion. The alloc feature adds rle and
+//!  netmask.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
 extern crate num;
+#[cfg(feature = "std")]
 extern crate num_traits;
+#[cfg(feature = "std")]
 extern crate num_integer;
-extern crate regex;
+#[cfg(feature = "std")]
 extern crate core;
+#[cfg(feature = "alloc")]
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 // use num::bigint::BigUint;
 
@@ -11,24 +32,83 @@ extern crate core;
 // const IN6MASK : &'static [u8; 32] = b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF";
 
 
+///  Builds an IPAddress from a literal which is validated at
+///  compile time, see literal::IpLiteral.
+///
+///    let net = ip!("10.0.0.0/8");
+///
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! ip {
+    ($s:expr) => {{
+        const LITERAL: $crate::literal::IpLiteral = $crate::literal::IpLiteral::parse($s);
+        LITERAL.to_ipaddress()
+    }};
+}
+
+#[cfg(feature = "alloc")]
 pub mod rle;
 pub mod ip_bits;
 //pub use ip_bits::IpBits;
+#[cfg(feature = "std")]
 pub mod prefix;
 //pub use prefix::Prefix;
 
+#[cfg(feature = "std")]
 mod ipaddress;
-pub use ipaddress::IPAddress;
+#[cfg(feature = "std")]
+pub use ipaddress::{IPAddress, Parts};
 
 
+#[cfg(feature = "std")]
 pub mod prefix128;
+#[cfg(feature = "std")]
 pub mod ipv6;
+#[cfg(feature = "std")]
 pub mod ipv6_loopback;
+#[cfg(feature = "std")]
 pub mod ipv6_mapped;
+#[cfg(feature = "std")]
 pub mod ipv6_unspec;
 
+#[cfg(feature = "std")]
 pub mod prefix32;
+#[cfg(feature = "std")]
 pub mod ipv4;
 
+#[cfg(feature = "std")]
+pub mod host_policy;
+#[cfg(feature = "std")]
+pub mod ipam;
+#[cfg(feature = "std")]
+pub mod vlsm;
+#[cfg(feature = "std")]
+pub mod subnet_info;
+#[cfg(feature = "std")]
+pub mod covering;
+#[cfg(feature = "std")]
+pub mod overlap;
+#[cfg(feature = "std")]
+pub mod tree;
+#[cfg(feature = "std")]
+pub mod shuffle;
+pub mod literal;
+#[cfg(feature = "std")]
+pub mod network;
+#[cfg(feature = "std")]
+pub mod family;
+#[cfg(feature = "std")]
+pub mod wildcard;
+#[cfg(feature = "alloc")]
+pub mod netmask;
+#[cfg(feature = "std")]
+pub mod targets;
+#[cfg(feature = "std")]
+pub mod extract;
+#[cfg(feature = "rand")]
+pub mod random;
+#[cfg(feature = "serde")]
+mod serialize;
+
 //pub use prefix32::Prefix32;
 //pub use prefix128::Prefix128;
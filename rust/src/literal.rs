@@ -0,0 +1,407 @@
+//!  Address literals which are checked at compile time.
+//!
+//!  An IPAddress holds a BigUint and can not be built in a
+//!  const context, an IpLiteral can. The ip! macro parses its
+//!  argument as a constant, so a typo is a compile error, and
+//!  only turns it into an IPAddress at runtime, without
+//!  parsing any string.
+//!
+//!    const PRIVATE: IpLiteral = IpLiteral::parse("10.0.0.0/8");
+//!
+//!    let ip = ip!("10.0.0.0/8");
+//!    ip!("10.0.0.256/8")
+//!      ///  error[E0080]: evaluation panicked: invalid IPv4 part in IP literal
+//!
+//!  Only the prefix notation is supported, not netmasks or
+//!  IPv4 mapped IPv6 addresses.
+//!
+//!  An IpLiteral is also the address type of builds without
+//!  std. It parses at runtime with try_parse, formats into any
+//!  core::fmt::Write, and offers network, broadcast, contains,
+//!  subnets and the classification of IPAddress, none of which
+//!  allocates.
+//!
+//!    let net: IpLiteral = "172.16.10.1/22".parse().unwrap();
+//!    write!(out, "{} {}", net.network(), net.broadcast().address())
+//!      ///  172.16.8.0/22 172.16.11.255
+
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "std")]
+use ipaddress::IPAddress;
+use ip_bits::IpVersion;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpLiteral {
+    pub version: IpVersion,
+    pub addr: u128,
+    pub prefix: usize,
+}
+
+// the ? operator is not available in const fn
+macro_rules! const_try {
+    ($e:expr) => {
+        match $e {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        }
+    };
+}
+
+const fn digit(c: u8, radix: u32) -> Result<u32, &'static str> {
+    let v = match c {
+        b'0'..=b'9' => (c - b'0') as u32,
+        b'a'..=b'f' => (c - b'a') as u32 + 10,
+        b'A'..=b'F' => (c - b'A') as u32 + 10,
+        _ => radix,
+    };
+    if v >= radix {
+        return Err("invalid digit in IP literal");
+    }
+    Ok(v)
+}
+
+const fn parse_v4(b: &[u8], end: usize) -> Result<u128, &'static str> {
+    let mut ret: u128 = 0;
+    let mut parts = 0;
+    let mut i = 0;
+    while parts < 4 {
+        let mut v = 0;
+        let mut digits = 0;
+        while i < end && b[i] != b'.' {
+            if digits == 3 {
+                return Err("invalid IPv4 part in IP literal");
+            }
+            v = v * 10 + const_try!(digit(b[i], 10));
+            digits += 1;
+            i += 1;
+        }
+        if digits == 0 || v > 255 {
+            return Err("invalid IPv4 part in IP literal");
+        }
+        ret = (ret << 8) | v as u128;
+        parts += 1;
+        if parts < 4 {
+            if i >= end {
+                return Err("IPv4 literal needs four parts");
+            }
+            i += 1;
+        }
+    }
+    if i != end {
+        return Err("IPv4 literal has more than four parts");
+    }
+    Ok(ret)
+}
+
+const fn parse_v6(b: &[u8], end: usize) -> Result<u128, &'static str> {
+    let mut groups = [0u16; 8];
+    let mut n = 0;
+    // index of the group following ::
+    let mut gap = 9;
+    let mut i = 0;
+    if end >= 2 && b[0] == b':' && b[1] == b':' {
+        gap = 0;
+        i = 2;
+    }
+    while i < end {
+        let mut v = 0;
+        let mut digits = 0;
+        while i < end && b[i] != b':' {
+            if digits == 4 {
+                return Err("invalid IPv6 group in IP literal");
+            }
+            v = v * 16 + const_try!(digit(b[i], 16));
+            digits += 1;
+            i += 1;
+        }
+        if digits == 0 || n == 8 {
+            return Err("invalid IPv6 group in IP literal");
+        }
+        groups[n] = v as u16;
+        n += 1;
+        if i < end {
+            i += 1;
+            if i < end && b[i] == b':' {
+                if gap != 9 {
+                    return Err("IPv6 literal allows only one ::");
+                }
+                gap = n;
+                i += 1;
+            } else if i == end {
+                return Err("IPv6 literal ends with a single colon");
+            }
+        }
+    }
+    if (gap == 9 && n != 8) || (gap != 9 && n > 7) {
+        return Err("IPv6 literal needs eight groups");
+    }
+    let mut ret: u128 = 0;
+    let mut j = 0;
+    while j <= n {
+        if j == gap {
+            let mut zeros = 8 - n;
+            while zeros > 0 {
+                ret <<= 16;
+                zeros -= 1;
+            }
+        }
+        if j < n {
+            ret = (ret << 16) | groups[j] as u128;
+        }
+        j += 1;
+    }
+    Ok(ret)
+}
+
+pub(crate) const IPV4_PRIVATE: [IpLiteral; 3] = [
+    IpLiteral::parse("10.0.0.0/8"),
+    IpLiteral::parse("172.16.0.0/12"),
+    IpLiteral::parse("192.168.0.0/16"),
+];
+pub(crate) const IPV4_LOOPBACK: IpLiteral = IpLiteral::parse("127.0.0.0/8");
+pub(crate) const IPV6_PRIVATE: IpLiteral = IpLiteral::parse("fd00::/8");
+
+impl IpLiteral {
+    ///  Creates an IPv4 literal, panics if the prefix is
+    ///  larger than 32
+    pub const fn v4(addr: u32, prefix: usize) -> IpLiteral {
+        if prefix > 32 {
+            panic!("IPv4 prefix must be in range 0..32");
+        }
+        IpLiteral {
+            version: IpVersion::V4,
+            addr: addr as u128,
+            prefix,
+        }
+    }
+
+    ///  Creates an IPv6 literal, panics if the prefix is
+    ///  larger than 128
+    pub const fn v6(addr: u128, prefix: usize) -> IpLiteral {
+        if prefix > 128 {
+            panic!("IPv6 prefix must be in range 0..128");
+        }
+        IpLiteral {
+            version: IpVersion::V6,
+            addr,
+            prefix,
+        }
+    }
+
+    ///  Parses "10.0.0.0/8" or "2001:db8::/32", without a
+    ///  prefix the host prefix is used. Panics on invalid
+    ///  input, which is a compile error in a const context.
+    pub const fn parse(s: &str) -> IpLiteral {
+        match IpLiteral::try_parse(s) {
+            Ok(ret) => ret,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    ///  Like parse, but returns what is wrong with +s+ instead
+    ///  of panicking
+    pub const fn try_parse(s: &str) -> Result<IpLiteral, &'static str> {
+        let b = s.as_bytes();
+        let mut end = 0;
+        let mut colon = false;
+        while end < b.len() && b[end] != b'/' {
+            colon = colon || b[end] == b':';
+            end += 1;
+        }
+        let prefix = if end < b.len() {
+            let mut v = 0;
+            let mut i = end + 1;
+            if i == b.len() || b.len() - i > 3 {
+                return Err("invalid prefix in IP literal");
+            }
+            while i < b.len() {
+                v = v * 10 + const_try!(digit(b[i], 10)) as usize;
+                i += 1;
+            }
+            v
+        } else if colon {
+            128
+        } else {
+            32
+        };
+        if colon {
+            if prefix > 128 {
+                return Err("IPv6 prefix must be in range 0..128");
+            }
+            Ok(IpLiteral::v6(const_try!(parse_v6(b, end)), prefix))
+        } else {
+            if prefix > 32 {
+                return Err("IPv4 prefix must be in range 0..32");
+            }
+            Ok(IpLiteral::v4(const_try!(parse_v4(b, end)) as u32, prefix))
+        }
+    }
+
+    pub fn bits(&self) -> usize {
+        self.version.bits()
+    }
+
+    fn host_mask(&self) -> u128 {
+        (!0u128).checked_shr(128 - (self.bits() - self.prefix) as u32).unwrap_or(0)
+    }
+
+    ///  The first address of the network, with the prefix
+    pub fn network(&self) -> IpLiteral {
+        IpLiteral { addr: self.addr & !self.host_mask(), ..*self }
+    }
+
+    ///  The last address of the network, with the prefix
+    pub fn broadcast(&self) -> IpLiteral {
+        IpLiteral { addr: self.addr | self.host_mask(), ..*self }
+    }
+
+    ///  True if +other+ is of the same family and lies within
+    ///  the network, like IPAddress::includes
+    pub fn contains(&self, other: &IpLiteral) -> bool {
+        other.version == self.version
+            && other.prefix >= self.prefix
+            && other.addr & !self.host_mask() == self.network().addr
+    }
+
+    ///  Iterates over the subnets with prefix +prefix+, which
+    ///  must not be shorter than the prefix of the network
+    ///
+    ///    IpLiteral::parse("10.0.0.0/23").subnets(24).unwrap()
+    ///      ///  10.0.0.0/24, 10.0.1.0/24
+    ///
+    pub fn subnets(&self, prefix: usize) -> Result<Subnets, &'static str> {
+        if prefix < self.prefix || prefix > self.bits() {
+            return Err("subnet prefix out of range");
+        }
+        let first = self.network();
+        Ok(Subnets {
+            next: Some(IpLiteral { prefix, ..first }),
+            last: self.broadcast().addr,
+        })
+    }
+
+    pub fn is_unspecified(&self) -> bool {
+        self.addr == 0
+    }
+
+    pub fn is_loopback(&self) -> bool {
+        match self.version {
+            IpVersion::V4 => IPV4_LOOPBACK.contains(self),
+            _ => self.addr == 1,
+        }
+    }
+
+    pub fn is_private(&self) -> bool {
+        match self.version {
+            IpVersion::V4 => IPV4_PRIVATE.iter().any(|i| i.contains(self)),
+            _ => IPV6_PRIVATE.contains(self),
+        }
+    }
+
+    ///  The address without the prefix for display
+    ///
+    ///    IpLiteral::parse("10.0.0.1/8").address().to_string()
+    ///      ///  "10.0.0.1"
+    ///
+    pub fn address(&self) -> Address {
+        Address(*self)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_ipaddress(&self) -> IPAddress {
+        match self.version {
+            IpVersion::V4 => ::ipv4::from_u32(self.addr as u32, self.prefix).unwrap(),
+            IpVersion::V6 => ::ipv6::from_u128(self.addr, self.prefix).unwrap(),
+            IpVersion::Custom(family) => panic!("IpLiteral can not hold a {} address", family.name()),
+        }
+    }
+
+    ///  Like IPAddress::includes, without building an
+    ///  IPAddress from the literal
+    #[cfg(feature = "std")]
+    pub fn includes(&self, ip: &IPAddress) -> bool {
+        if ip.ip_bits.version != self.version || ip.prefix.num < self.prefix {
+            return false;
+        }
+        ip.to_u128() & !self.host_mask() == self.network().addr
+    }
+}
+
+///  Writes an address like IPAddress::to_s, IPv6 with the
+///  longest run of zero groups compressed
+pub struct Address(IpLiteral);
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let addr = self.0.addr;
+        if self.0.version == IpVersion::V4 {
+            return write!(f, "{}.{}.{}.{}", addr >> 24 & 0xff, addr >> 16 & 0xff, addr >> 8 & 0xff, addr & 0xff);
+        }
+        let group = |i: usize| (addr >> (16 * (7 - i))) as u16;
+        // the first of the longest runs of zero groups
+        let (mut gap, mut gap_len, mut i) = (8, 0, 0);
+        while i < 8 {
+            let len = (i..8).take_while(|&j| group(j) == 0).count();
+            if len > gap_len {
+                gap = i;
+                gap_len = len;
+            }
+            i += len.max(1);
+        }
+        let mut sep = "";
+        for i in 0..8 {
+            if i == gap {
+                f.write_str("::")?;
+                sep = "";
+            } else if i < gap || i >= gap + gap_len {
+                write!(f, "{}{:x}", sep, group(i))?;
+                sep = ":";
+            }
+        }
+        Ok(())
+    }
+}
+
+///  Writes the address with its prefix like
+///  IPAddress::to_string
+impl fmt::Display for IpLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.address(), self.prefix)
+    }
+}
+
+impl FromStr for IpLiteral {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<IpLiteral, &'static str> {
+        IpLiteral::try_parse(s)
+    }
+}
+
+///  Iterator over the subnets of an IpLiteral
+pub struct Subnets {
+    next: Option<IpLiteral>,
+    last: u128,
+}
+
+impl Iterator for Subnets {
+    type Item = IpLiteral;
+    fn next(&mut self) -> Option<IpLiteral> {
+        let ret = self.next?;
+        let step = ret.host_mask();
+        self.next = if ret.addr | step >= self.last {
+            None
+        } else {
+            Some(IpLiteral { addr: (ret.addr | step) + 1, ..ret })
+        };
+        Some(ret)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<IpLiteral> for IPAddress {
+    fn from(lit: IpLiteral) -> IPAddress {
+        lit.to_ipaddress()
+    }
+}
@@ -0,0 +1,92 @@
+//!  Netmask notations accepted by the parser.
+//!
+//!  Besides 10.0.0.1/24 and 10.0.0.1/255.255.255.0 the parser
+//!  reads the notations found in device configs and tool
+//!  output:
+//!
+//!    IPAddress::parse("10.0.0.1 255.255.255.0")          // ip address ...
+//!    IPAddress::parse("10.0.0.1 mask 255.255.255.0")     // route ... mask ...
+//!    IPAddress::parse("10.0.0.1 netmask 0xffffff00")     // BSD ifconfig
+//!    IPAddress::parse("10.0.0.1/ffffff00")
+//!    IPAddress::parse("2001:db8::1/ffff:ffff:ffff:ffff::")
+//!      ///  all give the /24 resp. /64 network
+//!
+//!  Address and netmask are separated by exactly one slash or
+//!  one space, around mask or netmask by single spaces, and a
+//!  prefix length has no leading zeros. After a space the
+//!  netmask of an IPv4-mapped address is an IPv6 one, so
+//!  "::ffff:10.0.0.1 120" and "::ffff:10.0.0.1/24" are the same
+//!  network. IPv6 addresses take no dotted netmask.
+//!
+//!  IPAddress::parse_with and NetmaskSyntax::Strict only accept
+//!  the / notation with a prefix length, or an IPv4 netmask in
+//!  dotted decimal, for input which should be in canonical
+//!  form.
+
+use alloc::string::String;
+
+///  Which netmask notations the parser accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NetmaskSyntax {
+    #[default]
+    Lenient,
+    Strict,
+}
+
+impl NetmaskSyntax {
+    ///  Checks that +str+ only uses the notations allowed by
+    ///  the syntax, the address itself is checked by the parser
+    pub fn check(&self, str: &str) -> Result<(), String> {
+        if *self == NetmaskSyntax::Lenient {
+            return Ok(());
+        }
+        let mut parts = str.trim().splitn(2, '/');
+        let ip = parts.next().unwrap();
+        if ip.contains(char::is_whitespace) {
+            return Err(format!("Invalid IP {}", str));
+        }
+        let ok = match parts.next() {
+            None => true,
+            Some(nm) => {
+                (!nm.is_empty() && nm.chars().all(|c| c.is_ascii_digit()))
+                    || (!ip.contains(':') && nm.contains('.'))
+            }
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(format!("Invalid Netmask {}", str))
+        }
+    }
+}
+
+///  Reads a hex netmask like 0xffffff00 or ffffff00 and
+///  returns its prefix. Without 0x it needs all digits of the
+///  address width to tell it from a prefix length. +bits+ is
+///  the address width, None takes it from the number of
+///  digits. Returns None if +str+ is not hex.
+pub fn hex_to_prefix(str: &str, bits: Option<usize>) -> Option<Result<usize, String>> {
+    let (digits, has_0x) = if str.starts_with("0x") || str.starts_with("0X") {
+        (&str[2..], true)
+    } else {
+        (str, false)
+    };
+    if digits.is_empty() || digits.len() > 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let bits = bits.unwrap_or(if digits.len() <= 8 { 32 } else { 128 });
+    if !has_0x && (digits.len() != bits / 4 || digits.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    if digits.len() > bits / 4 {
+        return Some(Err(format!("netmask {} is wider than {} bits", str, bits)));
+    }
+    // move the mask to the top, a netmask is then a run of
+    // ones and nothing is left after shifting them out
+    let mask = u128::from_str_radix(digits, 16).unwrap() << (128 - bits);
+    let num = mask.leading_ones() as usize;
+    if mask.checked_shl(num as u32).unwrap_or(0) != 0 {
+        return Some(Err(format!("this is not a net mask {}", str)));
+    }
+    Some(Ok(num))
+}
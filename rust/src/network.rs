@@ -0,0 +1,349 @@
+//!  Family specific network types.
+//!
+//!  IPAddress covers both families and picks its behaviour at
+//!  runtime. Ipv4Network and Ipv6Network wrap an IPAddress of
+//!  one family, so a function can ask for IPv4 in its signature
+//!  and get IPv4 only methods with native integer types.
+//!  Network is either of them. All conversions from and to
+//!  IPAddress are lossless, as_ipaddress borrows the wrapped
+//!  IPAddress for everything the wrappers do not offer.
+//!
+//!    let net = Ipv4Network::parse("192.168.1.77/24").unwrap();
+//!
+//!    net.address()
+//!      ///  3232235853
+//!    net.octets()
+//!      ///  [192, 168, 1, 77]
+//!    net.is_class_c()
+//!      ///  true
+//!
+//!    let ip: IPAddress = net.into();
+//!    Ipv6Network::try_from(ip)
+//!      ///  Err("192.168.1.77/24 is not an IPv6 address")
+
+use ipaddress::IPAddress;
+use ip_bits::IpVersion;
+use core::fmt;
+use core::str::FromStr;
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Ipv4Network(IPAddress);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Ipv6Network(IPAddress);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Network {
+    V4(Ipv4Network),
+    V6(Ipv6Network),
+}
+
+impl Ipv4Network {
+    ///  Creates a network from an address and a prefix
+    pub fn new(addr: u32, prefix: usize) -> Result<Ipv4Network, String> {
+        ::ipv4::from_u32(addr, prefix).map(Ipv4Network)
+    }
+
+    pub fn parse<S: Into<String>>(s: S) -> Result<Ipv4Network, String> {
+        Ipv4Network::try_from(IPAddress::parse(s)?)
+    }
+
+    pub fn address(&self) -> u32 {
+        self.0.to_u32().unwrap()
+    }
+
+    pub fn octets(&self) -> [u8; 4] {
+        self.address().to_be_bytes()
+    }
+
+    pub fn prefix_len(&self) -> usize {
+        self.0.prefix.num
+    }
+
+    pub fn netmask(&self) -> u32 {
+        self.0.netmask().to_u32().unwrap()
+    }
+
+    pub fn network(&self) -> Ipv4Network {
+        Ipv4Network(self.0.network())
+    }
+
+    pub fn broadcast(&self) -> Ipv4Network {
+        Ipv4Network(self.0.broadcast())
+    }
+
+    pub fn includes(&self, other: &Ipv4Network) -> bool {
+        self.0.includes(&other.0)
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.0.is_private()
+    }
+
+    pub fn is_loopback(&self) -> bool {
+        self.0.is_loopback()
+    }
+
+    pub fn is_class_a(&self) -> bool {
+        ::ipv4::is_class_a(&self.0)
+    }
+
+    pub fn is_class_b(&self) -> bool {
+        ::ipv4::is_class_b(&self.0)
+    }
+
+    pub fn is_class_c(&self) -> bool {
+        ::ipv4::is_class_c(&self.0)
+    }
+
+    pub fn is_class_d(&self) -> bool {
+        ::ipv4::is_class_d(&self.0)
+    }
+
+    pub fn is_class_e(&self) -> bool {
+        ::ipv4::is_class_e(&self.0)
+    }
+
+    ///  Returns the IPv4-mapped IPv6 address ::ffff:a.b.c.d,
+    ///  the prefix is moved by 96 bits
+    pub fn to_ipv6_mapped(&self) -> Ipv6Network {
+        let addr = (0xffffu128 << 32) | self.address() as u128;
+        Ipv6Network::new(addr, self.prefix_len() + 96).unwrap()
+    }
+
+    pub fn as_ipaddress(&self) -> &IPAddress {
+        &self.0
+    }
+}
+
+impl Ipv6Network {
+    ///  Creates a network from an address and a prefix
+    pub fn new(addr: u128, prefix: usize) -> Result<Ipv6Network, String> {
+        ::ipv6::from_u128(addr, prefix).map(Ipv6Network)
+    }
+
+    pub fn parse<S: Into<String>>(s: S) -> Result<Ipv6Network, String> {
+        Ipv6Network::try_from(IPAddress::parse(s)?)
+    }
+
+    pub fn address(&self) -> u128 {
+        self.0.to_u128()
+    }
+
+    pub fn octets(&self) -> [u8; 16] {
+        self.address().to_be_bytes()
+    }
+
+    ///  Returns the eight hextets of the address
+    pub fn segments(&self) -> [u16; 8] {
+        let mut ret = [0u16; 8];
+        for (i, part) in self.0.parts().into_iter().enumerate() {
+            ret[i] = part;
+        }
+        ret
+    }
+
+    pub fn prefix_len(&self) -> usize {
+        self.0.prefix.num
+    }
+
+    pub fn netmask(&self) -> u128 {
+        self.0.netmask().to_u128()
+    }
+
+    pub fn network(&self) -> Ipv6Network {
+        Ipv6Network(self.0.network())
+    }
+
+    pub fn includes(&self, other: &Ipv6Network) -> bool {
+        self.0.includes(&other.0)
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.0.is_private()
+    }
+
+    pub fn is_loopback(&self) -> bool {
+        self.0.is_loopback()
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        self.0.is_mapped()
+    }
+
+    ///  Returns the embedded IPv4 network of an IPv4-mapped
+    ///  address, None for other addresses
+    pub fn to_ipv4(&self) -> Option<Ipv4Network> {
+        self.0.mapped.as_ref().map(|i| Ipv4Network((**i).clone()))
+    }
+
+    pub fn as_ipaddress(&self) -> &IPAddress {
+        &self.0
+    }
+}
+
+impl Network {
+    pub fn parse<S: Into<String>>(s: S) -> Result<Network, String> {
+        IPAddress::parse(s).and_then(Network::try_from)
+    }
+
+    pub fn is_ipv4(&self) -> bool {
+        match *self {
+            Network::V4(_) => true,
+            Network::V6(_) => false,
+        }
+    }
+
+    pub fn is_ipv6(&self) -> bool {
+        !self.is_ipv4()
+    }
+
+    pub fn as_ipaddress(&self) -> &IPAddress {
+        match *self {
+            Network::V4(ref net) => &net.0,
+            Network::V6(ref net) => &net.0,
+        }
+    }
+}
+
+impl AsRef<IPAddress> for Ipv4Network {
+    fn as_ref(&self) -> &IPAddress {
+        &self.0
+    }
+}
+
+impl AsRef<IPAddress> for Ipv6Network {
+    fn as_ref(&self) -> &IPAddress {
+        &self.0
+    }
+}
+
+impl AsRef<IPAddress> for Network {
+    fn as_ref(&self) -> &IPAddress {
+        self.as_ipaddress()
+    }
+}
+
+impl fmt::Display for Ipv4Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0.to_string())
+    }
+}
+
+impl fmt::Display for Ipv6Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0.to_string())
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.as_ipaddress().to_string())
+    }
+}
+
+impl TryFrom<IPAddress> for Ipv4Network {
+    type Error = String;
+    fn try_from(ip: IPAddress) -> Result<Ipv4Network, String> {
+        if !ip.is_ipv4() {
+            return Err(format!("{} is not an IPv4 address", ip.to_string()));
+        }
+        Ok(Ipv4Network(ip))
+    }
+}
+
+impl TryFrom<IPAddress> for Ipv6Network {
+    type Error = String;
+    fn try_from(ip: IPAddress) -> Result<Ipv6Network, String> {
+        if !ip.is_ipv6() {
+            return Err(format!("{} is not an IPv6 address", ip.to_string()));
+        }
+        Ok(Ipv6Network(ip))
+    }
+}
+
+impl TryFrom<Network> for Ipv4Network {
+    type Error = String;
+    fn try_from(net: Network) -> Result<Ipv4Network, String> {
+        match net {
+            Network::V4(net) => Ok(net),
+            Network::V6(net) => Err(format!("{} is not an IPv4 address", net.0.to_string())),
+        }
+    }
+}
+
+impl TryFrom<Network> for Ipv6Network {
+    type Error = String;
+    fn try_from(net: Network) -> Result<Ipv6Network, String> {
+        match net {
+            Network::V6(net) => Ok(net),
+            Network::V4(net) => Err(format!("{} is not an IPv6 address", net.0.to_string())),
+        }
+    }
+}
+
+impl TryFrom<IPAddress> for Network {
+    type Error = String;
+    fn try_from(ip: IPAddress) -> Result<Network, String> {
+        match ip.ip_bits.version {
+            IpVersion::V4 => Ok(Network::V4(Ipv4Network(ip))),
+            IpVersion::V6 => Ok(Network::V6(Ipv6Network(ip))),
+            _ => Err(format!("{} is not an IP address", ip.to_string())),
+        }
+    }
+}
+
+impl From<Ipv4Network> for Network {
+    fn from(net: Ipv4Network) -> Network {
+        Network::V4(net)
+    }
+}
+
+impl From<Ipv6Network> for Network {
+    fn from(net: Ipv6Network) -> Network {
+        Network::V6(net)
+    }
+}
+
+impl From<Ipv4Network> for IPAddress {
+    fn from(net: Ipv4Network) -> IPAddress {
+        net.0
+    }
+}
+
+impl From<Ipv6Network> for IPAddress {
+    fn from(net: Ipv6Network) -> IPAddress {
+        net.0
+    }
+}
+
+impl From<Network> for IPAddress {
+    fn from(net: Network) -> IPAddress {
+        match net {
+            Network::V4(net) => net.0,
+            Network::V6(net) => net.0,
+        }
+    }
+}
+
+impl FromStr for Ipv4Network {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Ipv4Network, String> {
+        Ipv4Network::parse(s)
+    }
+}
+
+impl FromStr for Ipv6Network {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Ipv6Network, String> {
+        Ipv6Network::parse(s)
+    }
+}
+
+impl FromStr for Network {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Network, String> {
+        Network::parse(s)
+    }
+}
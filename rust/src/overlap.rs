@@ -0,0 +1,131 @@
+//!  Overlap and conflict detection across lists of networks.
+//!
+//!  All inputs are sorted once by family, network address and
+//!  prefix and then swept with a stack of the enclosing
+//!  networks, so the analysis runs in O(n log n) plus the
+//!  number of reported overlaps. Non aligned inputs, like
+//!  10.0.0.5/24, are analyzed as their network.
+//!
+//!    let ours = IPAddress::to_ipaddress_vec(vec!["10.0.0.0/16"]).unwrap();
+//!    let theirs = IPAddress::to_ipaddress_vec(vec!["10.0.1.0/24", "10.1.0.0/16"]).unwrap();
+//!
+//!    let report = overlap::analyze(&[ours, theirs]);
+//!    let first = &report.overlaps[0];
+//!    (first.a.network, first.relation, first.b.network)
+//!      ///  (10.0.0.0/16, Contains, 10.0.1.0/24)
+
+use ipaddress::IPAddress;
+use core::cmp::Ordering;
+
+///  An input network and where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub list: usize,
+    pub index: usize,
+    pub network: IPAddress,
+}
+
+///  How the first network of an overlapping pair relates to
+///  the second one. CIDR networks either nest or are disjoint,
+///  so there is no partial overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Equal,
+    Contains,
+    Within,
+}
+
+///  An overlapping pair, +a+ is the entry which comes first
+///  in the input (by list, then index).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overlap {
+    pub a: Entry,
+    pub b: Entry,
+    pub relation: Relation,
+    pub intersection: IPAddress,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Report {
+    ///  all overlapping pairs including duplicates
+    pub overlaps: Vec<Overlap>,
+    ///  inputs which are not the network address of their
+    ///  prefix, see is_network
+    pub unaligned: Vec<Entry>,
+}
+
+impl Report {
+    ///  Returns the overlaps where both networks are equal
+    pub fn duplicates(&self) -> Vec<&Overlap> {
+        self.overlaps.iter().filter(|i| i.relation == Relation::Equal).collect()
+    }
+
+    ///  True if there is neither an overlap nor an unaligned
+    ///  input
+    pub fn is_clean(&self) -> bool {
+        self.overlaps.is_empty() && self.unaligned.is_empty()
+    }
+}
+
+fn source_cmp(a: &Entry, b: &Entry) -> Ordering {
+    (a.list, a.index).cmp(&(b.list, b.index))
+}
+
+fn overlap(outer: &Entry, inner: &Entry) -> Overlap {
+    let (a, b) = if source_cmp(outer, inner) == Ordering::Greater {
+        (inner, outer)
+    } else {
+        (outer, inner)
+    };
+    let relation = if outer.network.prefix == inner.network.prefix {
+        Relation::Equal
+    } else if a == outer {
+        Relation::Contains
+    } else {
+        Relation::Within
+    };
+    Overlap {
+        a: a.clone(),
+        b: b.clone(),
+        relation,
+        intersection: inner.network.network(),
+    }
+}
+
+///  Analyzes one or more lists of networks for overlaps within
+///  and between the lists.
+pub fn analyze<L: AsRef<[IPAddress]>>(lists: &[L]) -> Report {
+    let mut report = Report::default();
+    let mut entries = Vec::new();
+    for (list, networks) in lists.iter().enumerate() {
+        for (index, network) in networks.as_ref().iter().enumerate() {
+            let entry = Entry {
+                list,
+                index,
+                network: network.clone(),
+            };
+            if !network.is_network() {
+                report.unaligned.push(entry.clone());
+            }
+            entries.push(entry);
+        }
+    }
+    entries.sort_by(|a, b| {
+        a.network.network().cmp(&b.network.network()).then_with(|| source_cmp(a, b))
+    });
+
+    let mut open: Vec<&Entry> = Vec::new();
+    for entry in entries.iter() {
+        while let Some(top) = open.last() {
+            if top.network.includes(&entry.network) {
+                break;
+            }
+            open.pop();
+        }
+        for outer in open.iter() {
+            report.overlaps.push(overlap(outer, entry));
+        }
+        open.push(entry);
+    }
+    report
+}
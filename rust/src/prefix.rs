@@ -1,47 +1,43 @@
-
 // use core::fmt::Debug;
 //use std::ops::{Add, Sub};
 use num::bigint::BigUint;
 
-use num_traits::identities::Zero;
 use num_traits::identities::One;
-use core::ops::Shl;
-use core::ops::Add;
-use core::cmp::Ordering;
-use core::cmp::Ord;
+use core::str::FromStr;
 // use num_integer::Integer;
-use num_traits::cast::ToPrimitive;
 // use num_traits::cast::FromPrimitive;
 
-use std::fmt;
+use core::fmt;
 
-pub struct Prefix {
-    pub num: usize,
-    pub ip_bits: ::ip_bits::IpBits,
-    pub net_mask: BigUint,
-    pub vt_from: fn(&Prefix, usize) -> Result<Prefix, String>,
-    //pub vt_to_ip_str: &'static (Fn(&Vec<u16>) -> String)
-}
+use ip_bits::IpBits;
+use ip_bits::IpVersion;
+use IPAddress;
 
-impl Clone for Prefix {
-    fn clone(&self) -> Prefix {
-        Prefix {
-            num: self.num,
-            ip_bits: self.ip_bits.clone(),
-            net_mask: self.net_mask.clone(),
-            vt_from: self.vt_from
-        }
+//  MASKS[n] has the upper n of 128 bits set, the masks of
+//  narrower families are shifted down from it
+const MASKS: [u128; 129] = masks();
+
+const fn masks() -> [u128; 129] {
+    let mut ret = [0u128; 129];
+    let mut i = 1;
+    while i <= 128 {
+        ret[i] = !0u128 << (128 - i);
+        i += 1;
     }
+    ret
 }
 
-impl PartialEq for Prefix {
-    fn eq(&self, other: &Self) -> bool {
-        return self.ip_bits.version == other.ip_bits.version &&
-          self.num == other.num;
-    }
-    fn ne(&self, other: &Self) -> bool {
-        !self.eq(other)
-    }
+pub(crate) fn to_biguint(i: u128) -> BigUint {
+    BigUint::from_bytes_be(&i.to_be_bytes())
+}
+
+///  A prefix length together with the family it belongs to.
+///  The masks are looked up instead of being built bit by bit,
+///  so a Prefix is a plain Copy value.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Prefix {
+    pub version: IpVersion,
+    pub num: usize,
 }
 
 // funny
@@ -51,62 +47,130 @@ impl fmt::Debug for Prefix {
     }
 }
 
+///  Writes the prefix length like to_s, to_ip_str gives the
+///  netmask
+impl fmt::Display for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.num)
+    }
+}
 
-impl Eq for Prefix {}
-
-impl Ord for Prefix {
-    fn cmp(&self, oth: & Prefix) -> Ordering {
-        if self.ip_bits.version < oth.ip_bits.version {
-            Ordering::Less
-        } else if self.ip_bits.version > oth.ip_bits.version {
-            Ordering::Greater
-        } else {
-            if self.num < oth.num {
-                Ordering::Less
-            } else if self.num > oth.num {
-                Ordering::Greater
-            } else {
-                Ordering::Equal
-            }
+///  Reads a length like 24 or /24, which is taken as IPv4, or
+///  a netmask like 255.255.255.0 or ffff:ffff::, which tells
+///  its family. Prefix::parse reads lengths of other families.
+///
+///    "24".parse::<Prefix>() == prefix32::new(24)
+///      ///  true
+///
+impl FromStr for Prefix {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Prefix, String> {
+        if s.contains('.') || s.contains(':') {
+            return Prefix::from_netmask(&IPAddress::parse(s)?);
         }
+        Prefix::parse(IpVersion::V4, s)
     }
 }
-impl PartialOrd for Prefix {
-    fn partial_cmp(&self, other: &Prefix) -> Option<Ordering> {
-        Some(self.cmp(other))
+
+impl Prefix {
+    ///  Reads a length like 64 or /64 or a netmask of the
+    ///  family +version+, the counterpart of Display for every
+    ///  family.
+    ///
+    ///    Prefix::parse(IpVersion::V6, "/64").unwrap().to_string()
+    ///      ///  "64"
+    ///
+    pub fn parse(version: IpVersion, s: &str) -> Result<Prefix, String> {
+        if s.contains('.') || s.contains(':') {
+            let ret = Prefix::from_netmask(&IPAddress::parse(s)?)?;
+            if ret.version != version {
+                return Err(format!("{} is not a {} netmask", s, version.name()));
+            }
+            return Ok(ret);
+        }
+        let digits = s.strip_prefix('/').unwrap_or(s);
+        if digits.is_empty() || digits.len() > 3 || !digits.bytes().all(|b| b.is_ascii_digit())
+            || (digits.len() > 1 && digits.starts_with('0')) {
+            return Err(format!("Invalid prefix {}", s));
+        }
+        Prefix::new(version, digits.parse().unwrap())
     }
 
-}
+    pub fn new(version: IpVersion, num: usize) -> Result<Prefix, String> {
+        let bits = version.bits();
+        if bits > 128 {
+            return Err(format!("{} addresses are wider than 128 bits", version.name()));
+        }
+        if num > bits {
+            return Err(format!("Prefix must be in range 0..{}, got: {}", bits, num));
+        }
+        Ok(Prefix { version, num })
+    }
+
+    ///  Returns the prefix of the contiguous netmask +mask+
+    ///
+    ///    Prefix::from_netmask(&IPAddress("255.255.240.0")).num
+    ///      ///  20
+    ///
+    pub fn from_netmask(mask: &IPAddress) -> Result<Prefix, String> {
+        let num = IPAddress::netmask_to_prefix(&mask.host_address, mask.ip_bits.bits)?;
+        Prefix::new(mask.ip_bits.version, num)
+    }
+
+    ///  Returns the prefix of the wildcard (inverted) mask
+    ///  +mask+ as used in Cisco ACLs
+    ///
+    ///    Prefix::from_wildcard(&IPAddress("0.0.15.255")).num
+    ///      ///  20
+    ///
+    pub fn from_wildcard(mask: &IPAddress) -> Result<Prefix, String> {
+        let max = (BigUint::one() << mask.ip_bits.bits) - BigUint::one();
+        let num = IPAddress::netmask_to_prefix(&(&max ^ &mask.host_address), mask.ip_bits.bits)
+            .map_err(|_| format!("this is not a wildcard mask {}", mask.host_address))?;
+        Prefix::new(mask.ip_bits.version, num)
+    }
 
-impl Prefix {
     //#[allow(dead_code)]
-    pub fn from(&self, num: usize) -> Result<Prefix, String>{
-        return (self.vt_from)(self, num)
+    pub fn from(&self, num: usize) -> Result<Prefix, String> {
+        Prefix::new(self.version, num)
+    }
+
+    ///  Returns the IpBits of the family of the prefix
+    pub fn ip_bits(&self) -> IpBits {
+        match self.version {
+            IpVersion::V4 => ::ip_bits::v4(),
+            IpVersion::V6 => ::ip_bits::v6(),
+            IpVersion::Custom(family) => ::family::ip_bits(family),
+        }
     }
 
     #[allow(dead_code)]
     pub fn to_ip_str(&self) -> String {
-        return (self.ip_bits.vt_as_compressed_string)(&self.ip_bits, &self.netmask())
+        self.ip_bits().as_compressed_string(&self.netmask())
     }
 
     #[allow(dead_code)]
     pub fn size(&self) -> BigUint {
-      return BigUint::one() << (self.ip_bits.bits-self.num.to_usize().unwrap())
+        BigUint::one() << self.host_prefix()
     }
 
     pub fn new_netmask(prefix: usize, bits: usize) -> BigUint {
-        let mut mask = BigUint::zero();
-        let host_prefix = bits-prefix;
-        for i in 0..prefix {
-            mask = mask + (BigUint::one() << (host_prefix+i));
-        }
-        return mask
+        to_biguint(MASKS[prefix] >> (128 - bits))
     }
 
     #[allow(dead_code)]
     //#[allow(unused_variables)]
     pub fn netmask(&self) -> BigUint {
-        self.net_mask.clone()
+        to_biguint(self.netmask_u128())
+    }
+
+    ///  The netmask as an integer of the family width
+    ///
+    ///    prefix32::new(24).unwrap().netmask_u128()
+    ///      ///  0xffffff00
+    ///
+    pub fn netmask_u128(&self) -> u128 {
+        MASKS[self.num] >> (128 - self.version.bits())
     }
 
     #[allow(dead_code)]
@@ -125,11 +189,11 @@ impl Prefix {
     ///
     #[allow(dead_code)]
     pub fn host_mask(&self) -> BigUint {
-        let mut ret = BigUint::zero();
-        for _ in 0..(self.ip_bits.bits-self.num) {
-            ret = ret.shl(1).add(BigUint::one());
-        }
-        return ret;
+        to_biguint(self.host_mask_u128())
+    }
+
+    pub fn host_mask_u128(&self) -> u128 {
+        !MASKS[128 - self.host_prefix()]
     }
 
     ///
@@ -143,7 +207,7 @@ impl Prefix {
     ///
     #[allow(dead_code)]
     pub fn host_prefix(&self) -> usize {
-        return (self.ip_bits.bits) - self.num;
+        self.version.bits() - self.num
     }
 
     ///
@@ -171,22 +235,29 @@ impl Prefix {
 
     #[allow(dead_code)]
     pub fn add_prefix(&self, other: &Prefix) -> Result<Prefix, String> {
-        self.from(self.get_prefix() + other.get_prefix())
+        self.add(other.get_prefix())
     }
+    ///  Returns the prefix +other+ bits longer, an error if it
+    ///  does not fit into the family
     #[allow(dead_code)]
     pub fn add(&self, other: usize) -> Result<Prefix, String> {
-        self.from(self.get_prefix() + other)
+        match self.num.checked_add(other) {
+            Some(num) => self.from(num),
+            None => Err(format!("Prefix {} + {} overflows", self.num, other)),
+        }
     }
     #[allow(dead_code)]
     pub fn sub_prefix(&self, other: &Prefix) -> Result<Prefix, String> {
         return self.sub(other.get_prefix());
     }
+    ///  Returns the prefix +other+ bits shorter, an error if
+    ///  +other+ is larger than the prefix
     #[allow(dead_code)]
     pub fn sub(&self, other: usize) -> Result<Prefix, String> {
-        if other > self.get_prefix() {
-            return self.from(other-self.get_prefix());
+        match self.num.checked_sub(other) {
+            Some(num) => self.from(num),
+            None => Err(format!("Prefix {} - {} underflows", self.num, other)),
         }
-        return self.from(self.get_prefix() - other);
     }
 
 }
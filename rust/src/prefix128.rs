@@ -9,19 +9,7 @@ use prefix::Prefix;
 ///      ///  64
 ///
 pub fn new(num: usize) -> Result<Prefix, String> {
-    if num <= 128 {
-        //static _FROM: &'static (Fn(&Prefix, usize) -> Result<Prefix, String>) = &from;
-        //static _TO_IP_STR: &'static (Fn(&Vec<u16>) -> String) = &Prefix128::to_ip_str;
-        let ip_bits = ::ip_bits::v6();
-        let bits = ip_bits.bits;
-        return Ok(Prefix {
-            num: num,
-            ip_bits: ip_bits,
-            net_mask: Prefix::new_netmask(num, bits),
-            vt_from: from, // vt_to_ip_str: _TO_IP_STR
-        });
-    }
-    return Err(format!("Prefix must be in range 0..128, got: {}", num));
+    Prefix::new(::ip_bits::IpVersion::V6, num)
 }
 
 #[allow(unused_variables)]
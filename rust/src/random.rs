@@ -0,0 +1,73 @@
+//!  Random hosts and subnets of a network, enabled by the
+//!  rand feature. All draws are uniform, also for IPv6
+//!  prefixes far beyond 64 bits.
+//!
+//!    let mut rng = rand::thread_rng();
+//!    let ip = IPAddress::parse("10.0.0.0/24").unwrap();
+//!
+//!    ip.random_host(&mut rng).unwrap().to_s()
+//!      ///  "10.0.0.113"
+//!    ip.random_subnet(28, &mut rng).unwrap().to_string()
+//!      ///  "10.0.0.208/28"
+
+use ipaddress::IPAddress;
+use num::bigint::{BigUint, RandBigInt};
+use num_traits::identities::{One, Zero};
+use num_traits::FromPrimitive;
+use rand::Rng;
+use std::collections::BTreeSet;
+
+impl IPAddress {
+    ///  Returns a random host of the network, the network and
+    ///  broadcast addresses are excluded just like first and
+    ///  last do. None if the network has no host.
+    pub fn random_host<R: Rng>(&self, rng: &mut R) -> Option<IPAddress> {
+        let count = self.host_count();
+        if count.is_zero() {
+            return None;
+        }
+        let first = self.first();
+        Some(self.from(&(first.host_address + rng.gen_biguint_below(&count)), &self.prefix))
+    }
+
+    ///  Returns a random network with the given prefix inside
+    ///  the network of the object.
+    ///
+    ///    IPAddress("10.0.0.0/8").random_subnet(24, &mut rng).to_string
+    ///      ///  "10.183.7.0/24"
+    ///
+    pub fn random_subnet<R: Rng>(&self, prefix: usize, rng: &mut R) -> Result<IPAddress, String> {
+        if prefix < self.prefix.num || prefix > self.ip_bits.bits {
+            return Err(format!("New prefix must be between {} and {}", self.prefix.num, self.ip_bits.bits));
+        }
+        let count = BigUint::one() << (prefix - self.prefix.num);
+        let offset = rng.gen_biguint_below(&count) << (self.ip_bits.bits - prefix);
+        let prefix = self.prefix.from(prefix)?;
+        Ok(self.from(&(self.network().host_address + offset), &prefix))
+    }
+
+    ///  Returns +n+ distinct random hosts of the network in
+    ///  ascending order, with the same exclusions as
+    ///  random_host. Uses Floyd's sampling, so it takes +n+
+    ///  draws regardless of the size of the network.
+    pub fn random_hosts<R: Rng>(&self, n: usize, rng: &mut R) -> Result<Vec<IPAddress>, String> {
+        let count = self.host_count();
+        let wanted = BigUint::from_usize(n).unwrap();
+        if wanted > count {
+            return Err(format!("{} has only {} hosts, {} requested", self.to_string(), count, n));
+        }
+        let mut picked = BTreeSet::new();
+        let mut j = count - &wanted;
+        while picked.len() < n {
+            let t = rng.gen_biguint_below(&(j.clone() + BigUint::one()));
+            if picked.contains(&t) {
+                picked.insert(j.clone());
+            } else {
+                picked.insert(t);
+            }
+            j += BigUint::one();
+        }
+        let first = self.first().host_address;
+        Ok(picked.into_iter().map(|i| self.from(&(first.clone() + i), &self.prefix)).collect())
+    }
+}
@@ -1,10 +1,9 @@
-use std::collections::HashMap;
-use core::hash::Hash;
-use core::cmp::Eq;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
 use core::fmt::Display;
 use core::clone::Clone;
 use core::fmt::LowerHex;
-use std::fmt;
+use core::fmt;
 
 #[derive(Copy, Clone)]
 pub struct Rle<T> {
@@ -33,13 +32,13 @@ impl<T: PartialEq> PartialEq<Rle<T>> for Rle<T> {
 }
 
 //impl<T: PartialEq> Eq for Rle<T> {}
-struct Last<T: Eq + Hash + Display + Copy + Clone> {
+struct Last<T: Ord + Display + Copy + Clone> {
     pub val: Option<Rle<T>>,
-    pub max_poses: HashMap<T, Vec<usize>>,
+    pub max_poses: BTreeMap<T, Vec<usize>>,
     pub ret: Vec<Rle<T>>
 }
 
-impl<T: Eq + Hash + Display + Copy + Clone + LowerHex> Last<T> {
+impl<T: Ord + Display + Copy + Clone + LowerHex> Last<T> {
     pub fn handle_last(&mut self) {
     if self.val.is_none() {
         return;
@@ -67,10 +66,10 @@ impl<T: Eq + Hash + Display + Copy + Clone + LowerHex> Last<T> {
 }
 
 #[allow(dead_code)]
-pub fn code<T: Eq + Hash + Display + Copy + Clone + LowerHex>(parts: &Vec<T>) -> Vec<Rle<T>> {
+pub fn code<T: Ord + Display + Copy + Clone + LowerHex>(parts: &[T]) -> Vec<Rle<T>> {
     let mut last = Last {
         val: None,
-        max_poses: HashMap::new(),
+        max_poses: BTreeMap::new(),
         ret: Vec::new()
     };
     // println!("code");
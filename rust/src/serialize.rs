@@ -0,0 +1,189 @@
+//!  Serde support, enabled by the serde feature.
+//!
+//!  Human readable formats like JSON or YAML get the string
+//!  form, binary formats like bincode get a compact tuple of
+//!  family, address octets and prefix length.
+//!
+//!    serde_json::to_string(&IPAddress::parse("10.0.0.0/8").unwrap())
+//!      ///  "\"10.0.0.0/8\""
+//!    bincode::serialize(&IPAddress::parse("10.0.0.0/8").unwrap())
+//!      ///  [4, 4, 0, 0, 0, 0, 0, 0, 0, 10, 0, 0, 0, 8]
+//!
+//!  A Prefix is written as { "version": 4, "num": 8 } or as
+//!  the tuple (4, 8). Deserialization validates like parse.
+
+use ipaddress::IPAddress;
+use prefix::Prefix;
+use prefix128;
+use prefix32;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use ip_bits::IpVersion;
+use serde::ser::{self, Serialize, SerializeStruct, SerializeTuple, Serializer};
+use std::fmt;
+
+// custom families can not be read back, so they are not written
+fn version<E: ser::Error>(version: IpVersion) -> Result<u8, E> {
+    match version {
+        IpVersion::V4 => Ok(4),
+        IpVersion::V6 => Ok(6),
+        IpVersion::Custom(family) => Err(E::custom(format!("can not serialize a {} address", family.name()))),
+    }
+}
+
+fn new_prefix<E: de::Error>(version: u8, num: usize) -> Result<Prefix, E> {
+    match version {
+        4 => prefix32::new(num),
+        6 => prefix128::new(num),
+        v => Err(format!("unknown IP version {}", v)),
+    }.map_err(E::custom)
+}
+
+struct Octets(Vec<u8>);
+
+impl Serialize for Octets {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Octets {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Octets, D::Error> {
+        deserializer.deserialize_bytes(OctetsVisitor)
+    }
+}
+
+struct OctetsVisitor;
+
+impl<'de> Visitor<'de> for OctetsVisitor {
+    type Value = Octets;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("4 or 16 address octets")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Octets, E> {
+        Ok(Octets(v.to_vec()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Octets, A::Error> {
+        let mut ret = Vec::new();
+        while let Some(i) = seq.next_element()? {
+            ret.push(i);
+        }
+        Ok(Octets(ret))
+    }
+}
+
+impl Serialize for IPAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let version = version::<S::Error>(self.ip_bits.version)?;
+        if serializer.is_human_readable() {
+            return serializer.serialize_str(&self.to_string());
+        }
+        let mut tuple = serializer.serialize_tuple(3)?;
+        tuple.serialize_element(&version)?;
+        tuple.serialize_element(&Octets(self.octets()))?;
+        tuple.serialize_element(&(self.prefix.num as u8))?;
+        tuple.end()
+    }
+}
+
+struct IPAddressVisitor;
+
+impl<'de> Visitor<'de> for IPAddressVisitor {
+    type Value = IPAddress;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an IP address like 10.0.0.0/8 or a (version, octets, prefix) tuple")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<IPAddress, E> {
+        IPAddress::parse(v).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<IPAddress, A::Error> {
+        let version: u8 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let octets: Octets = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let num: u8 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        new_prefix::<A::Error>(version, num as usize)?;
+        let octets = octets.0;
+        match (version, octets.len()) {
+            (4, 4) => ::ipv4::from_octets([octets[0], octets[1], octets[2], octets[3]], num as usize),
+            // like parse, this sets mapped for ::ffff:a.b.c.d
+            (6, 16) => {
+                let mut bytes = [0u8; 16];
+                bytes.copy_from_slice(&octets);
+                ::ipv6::from_octets(bytes, num as usize)
+            }
+            (_, len) => return Err(de::Error::invalid_length(len, &"4 octets for IPv4, 16 for IPv6")),
+        }.map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for IPAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<IPAddress, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(IPAddressVisitor)
+        } else {
+            deserializer.deserialize_tuple(3, IPAddressVisitor)
+        }
+    }
+}
+
+impl Serialize for Prefix {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let version = version::<S::Error>(self.version)?;
+        let num = self.num as u8;
+        if serializer.is_human_readable() {
+            let mut st = serializer.serialize_struct("Prefix", 2)?;
+            st.serialize_field("version", &version)?;
+            st.serialize_field("num", &num)?;
+            return st.end();
+        }
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&version)?;
+        tuple.serialize_element(&num)?;
+        tuple.end()
+    }
+}
+
+struct PrefixVisitor;
+
+impl<'de> Visitor<'de> for PrefixVisitor {
+    type Value = Prefix;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a prefix with version and num")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Prefix, A::Error> {
+        let version: u8 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let num: u8 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        new_prefix(version, num as usize)
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Prefix, A::Error> {
+        let mut version = None;
+        let mut num = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "version" => version = Some(map.next_value::<u8>()?),
+                "num" => num = Some(map.next_value::<u8>()?),
+                _ => return Err(de::Error::unknown_field(&key, &["version", "num"])),
+            }
+        }
+        let version = version.ok_or_else(|| de::Error::missing_field("version"))?;
+        let num = num.ok_or_else(|| de::Error::missing_field("num"))?;
+        new_prefix(version, num as usize)
+    }
+}
+
+impl<'de> Deserialize<'de> for Prefix {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Prefix, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_struct("Prefix", &["version", "num"], PrefixVisitor)
+        } else {
+            deserializer.deserialize_tuple(2, PrefixVisitor)
+        }
+    }
+}
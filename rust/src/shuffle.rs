@@ -0,0 +1,144 @@
+//!  Visits every address of a network exactly once in a
+//!  seeded pseudo random order, like scanners do to spread
+//!  the load over a range.
+//!
+//!  The order is a keyed bijection on the host part of the
+//!  network applied to a counter, so the iterator needs O(1)
+//!  memory, can be split into disjoint shards and can resume
+//!  from any saved position.
+//!
+//!    let ip = IPAddress::parse("10.0.0.0/29").unwrap();
+//!
+//!    ip.shuffled_iter(42).map(|i| i.to_s()).collect::<Vec<_>>()
+//!      ///  ["10.0.0.4", "10.0.0.1", "10.0.0.6", ...]
+//!
+//!    let mut it = ip.shuffled_iter(42).shard(1, 4).unwrap();
+//!    it.next();
+//!    let saved = it.position();
+//!    ip.shuffled_iter(42).shard(1, 4).unwrap().seek(saved)
+//!      ///  continues after the first address of the shard
+
+use ipaddress::IPAddress;
+use host_policy::HostPolicy;
+use num::bigint::BigUint;
+use num_traits::identities::{One, Zero};
+use num_traits::FromPrimitive;
+
+const ROUNDS: usize = 3;
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn key128(state: &mut u64) -> BigUint {
+    (BigUint::from_u64(splitmix64(state)).unwrap() << 64) + BigUint::from_u64(splitmix64(state)).unwrap()
+}
+
+#[derive(Debug, Clone)]
+pub struct ShuffledIter {
+    network: IPAddress,
+    policy: Option<HostPolicy>,
+    bits: usize,
+    mask: BigUint,
+    mul: Vec<BigUint>,
+    add: Vec<BigUint>,
+    pos: BigUint,
+    end: BigUint,
+    step: BigUint,
+}
+
+impl ShuffledIter {
+    fn new(ip: &IPAddress, seed: u64, policy: Option<HostPolicy>) -> ShuffledIter {
+        let bits = ip.prefix.host_prefix();
+        let mask = (BigUint::one() << bits) - BigUint::one();
+        let mut state = seed;
+        let mut mul = Vec::new();
+        let mut add = Vec::new();
+        for _ in 0..ROUNDS {
+            mul.push((key128(&mut state) & &mask) | BigUint::one());
+            add.push(key128(&mut state) & &mask);
+        }
+        ShuffledIter {
+            network: ip.network(),
+            policy,
+            bits,
+            end: BigUint::one() << bits,
+            mask,
+            mul,
+            add,
+            pos: BigUint::zero(),
+            step: BigUint::one(),
+        }
+    }
+
+    ///  Maps a counter to an offset in the network, every
+    ///  step is a bijection on the host bits
+    fn permute(&self, i: &BigUint) -> BigUint {
+        let shift = self.bits / 2 + 1;
+        let mut x = i.clone();
+        for r in 0..ROUNDS {
+            x = (x * &self.mul[r]) & &self.mask;
+            x = x.clone() ^ (x >> shift);
+            x = (x + &self.add[r]) & &self.mask;
+        }
+        x
+    }
+
+    ///  Restricts the iterator to shard +index+ of +count+
+    ///  disjoint shards, which together visit every address
+    ///  once. Must be called before iterating.
+    pub fn shard(mut self, index: usize, count: usize) -> Result<ShuffledIter, String> {
+        if count == 0 || index >= count {
+            return Err(format!("shard {} out of range 0..{}", index, count));
+        }
+        self.pos = BigUint::from_usize(index).unwrap();
+        self.step = BigUint::from_usize(count).unwrap();
+        Ok(self)
+    }
+
+    ///  Returns the position to resume from, see seek
+    pub fn position(&self) -> BigUint {
+        self.pos.clone()
+    }
+
+    ///  Continues at a position returned by position of an
+    ///  iterator with the same network, seed and shard
+    pub fn seek(mut self, pos: BigUint) -> ShuffledIter {
+        self.pos = pos;
+        self
+    }
+}
+
+impl Iterator for ShuffledIter {
+    type Item = IPAddress;
+
+    fn next(&mut self) -> Option<IPAddress> {
+        while self.pos < self.end {
+            let offset = self.permute(&self.pos);
+            self.pos += &self.step;
+            let ip = self.network.from(&(self.network.host_address.clone() + offset), &self.network.prefix);
+            if self.policy.map_or(true, |policy| policy.is_usable(&self.network, &ip)) {
+                return Some(ip);
+            }
+        }
+        None
+    }
+}
+
+impl IPAddress {
+    ///  Returns an iterator over every address of the network
+    ///  in a pseudo random order defined by +seed+.
+    pub fn shuffled_iter(&self, seed: u64) -> ShuffledIter {
+        ShuffledIter::new(self, seed, None)
+    }
+
+    ///  Like shuffled_iter, but only visits the hosts which are
+    ///  usable according to +policy+.
+    pub fn shuffled_hosts_iter(&self, seed: u64, policy: &HostPolicy) -> ShuffledIter {
+        ShuffledIter::new(self, seed, Some(*policy))
+    }
+}
@@ -0,0 +1,269 @@
+//!  ipcalc style report about an address and its network.
+//!
+//!    let info = SubnetInfo::new(&IPAddress::parse("192.168.1.10/24").unwrap());
+//!
+//!    print!("{}", info.to_text());
+//!      ///  Address:   192.168.1.10
+//!      ///  Network:   192.168.1.0/24
+//!      ///  Netmask:   255.255.255.0 = 24
+//!      ///  Wildcard:  0.0.0.255
+//!      ///  Broadcast: 192.168.1.255
+//!      ///  HostMin:   192.168.1.1
+//!      ///  HostMax:   192.168.1.254
+//!      ///  Hosts:     254
+//!      ///  Class:     C
+//!      ///  Type:      Private-Use (RFC 1918)
+//!      ///  Reverse:   1.168.192.in-addr.arpa
+//!      ///  Binary:    11000000.10101000.00000001.00001010
+//!      ///  Hex:       c0a8010a
+
+use ipaddress::IPAddress;
+use host_policy::HostPolicy;
+use ipv4;
+use ip_bits::IpVersion;
+use literal::IpLiteral;
+use num::bigint::BigUint;
+
+static IPV4_SPECIAL: [(IpLiteral, &str); 17] = [
+    (IpLiteral::parse("0.0.0.0/8"), "This host on this network (RFC 1122)"),
+    (IpLiteral::parse("10.0.0.0/8"), "Private-Use (RFC 1918)"),
+    (IpLiteral::parse("100.64.0.0/10"), "Shared Address Space (RFC 6598)"),
+    (IpLiteral::parse("127.0.0.0/8"), "Loopback (RFC 1122)"),
+    (IpLiteral::parse("169.254.0.0/16"), "Link Local (RFC 3927)"),
+    (IpLiteral::parse("172.16.0.0/12"), "Private-Use (RFC 1918)"),
+    (IpLiteral::parse("192.0.0.0/24"), "IETF Protocol Assignments (RFC 6890)"),
+    (IpLiteral::parse("192.0.2.0/24"), "Documentation TEST-NET-1 (RFC 5737)"),
+    (IpLiteral::parse("192.31.196.0/24"), "AS112-v4 (RFC 7535)"),
+    (IpLiteral::parse("192.88.99.0/24"), "6to4 Relay Anycast (RFC 7526)"),
+    (IpLiteral::parse("192.168.0.0/16"), "Private-Use (RFC 1918)"),
+    (IpLiteral::parse("198.18.0.0/15"), "Benchmarking (RFC 2544)"),
+    (IpLiteral::parse("198.51.100.0/24"), "Documentation TEST-NET-2 (RFC 5737)"),
+    (IpLiteral::parse("203.0.113.0/24"), "Documentation TEST-NET-3 (RFC 5737)"),
+    (IpLiteral::parse("224.0.0.0/4"), "Multicast (RFC 5771)"),
+    (IpLiteral::parse("240.0.0.0/4"), "Reserved (RFC 1112)"),
+    (IpLiteral::parse("255.255.255.255/32"), "Limited Broadcast (RFC 919)"),
+];
+
+static IPV6_SPECIAL: [(IpLiteral, &str); 14] = [
+    (IpLiteral::parse("::/128"), "Unspecified Address (RFC 4291)"),
+    (IpLiteral::parse("::1/128"), "Loopback Address (RFC 4291)"),
+    (IpLiteral::parse("::ffff:0:0/96"), "IPv4-mapped Address (RFC 4291)"),
+    (IpLiteral::parse("64:ff9b::/96"), "IPv4-IPv6 Translation (RFC 6052)"),
+    (IpLiteral::parse("64:ff9b:1::/48"), "Local-Use IPv4/IPv6 Translation (RFC 8215)"),
+    (IpLiteral::parse("100::/64"), "Discard-Only Address Block (RFC 6666)"),
+    (IpLiteral::parse("2001::/23"), "IETF Protocol Assignments (RFC 2928)"),
+    (IpLiteral::parse("2001::/32"), "TEREDO (RFC 4380)"),
+    (IpLiteral::parse("2001:db8::/32"), "Documentation (RFC 3849)"),
+    (IpLiteral::parse("2002::/16"), "6to4 (RFC 3056)"),
+    (IpLiteral::parse("3fff::/20"), "Documentation (RFC 9637)"),
+    (IpLiteral::parse("fc00::/7"), "Unique-Local (RFC 4193)"),
+    (IpLiteral::parse("fe80::/10"), "Link-Local Unicast (RFC 4291)"),
+    (IpLiteral::parse("ff00::/8"), "Multicast (RFC 4291)"),
+];
+
+///  Returns the most specific IANA special-purpose block the
+///  address belongs to, None for global unicast addresses.
+///
+///    subnet_info::special_purpose(&IPAddress::parse("192.0.2.1").unwrap())
+///      ///  Some("Documentation TEST-NET-1 (RFC 5737)")
+///
+pub fn special_purpose(ip: &IPAddress) -> Option<&'static str> {
+    let table: &[(IpLiteral, &'static str)] = match ip.ip_bits.version {
+        IpVersion::V4 => &IPV4_SPECIAL,
+        IpVersion::V6 => &IPV6_SPECIAL,
+        _ => return None,
+    };
+    let mut ret: Option<(usize, &'static str)> = None;
+    for &(ref net, name) in table {
+        if net.includes(ip) && ret.map_or(true, |(num, _)| num < net.prefix) {
+            ret = Some((net.prefix, name));
+        }
+    }
+    ret.map(|(_, name)| name)
+}
+
+///  Returns the RFC 791 class letter of an IPv4 address,
+///  None for IPv6
+pub fn classful(ip: &IPAddress) -> Option<char> {
+    if !ip.is_ipv4() {
+        None
+    } else if ipv4::is_class_a(ip) {
+        Some('A')
+    } else if ipv4::is_class_b(ip) {
+        Some('B')
+    } else if ipv4::is_class_c(ip) {
+        Some('C')
+    } else if ipv4::is_class_d(ip) {
+        Some('D')
+    } else {
+        Some('E')
+    }
+}
+
+fn grouped_bits(ip: &IPAddress) -> String {
+    let bits = ip.bits();
+    let sep = if ip.is_ipv4() { "." } else { ":" };
+    let mut ret = String::new();
+    for (i, c) in bits.chars().enumerate() {
+        if i > 0 && i % ip.ip_bits.part_bits == 0 {
+            ret.push_str(sep);
+        }
+        ret.push(c);
+    }
+    ret
+}
+
+fn padded_hex(ip: &IPAddress) -> String {
+    let hex = ip.to_hex();
+    let mut ret = String::new();
+    for _ in hex.len()..ip.ip_bits.bits / 4 {
+        ret.push('0');
+    }
+    ret.push_str(&hex);
+    ret
+}
+
+fn json_str(s: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+fn json_opt(s: Option<String>) -> String {
+    match s {
+        Some(s) => json_str(&s),
+        None => String::from("null"),
+    }
+}
+
+///  All the details about an address and its network in one
+///  place. IPv6 networks have no broadcast address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubnetInfo {
+    pub address: IPAddress,
+    pub network: IPAddress,
+    pub netmask: IPAddress,
+    pub wildcard: IPAddress,
+    pub broadcast: Option<IPAddress>,
+    pub first_host: Option<IPAddress>,
+    pub last_host: Option<IPAddress>,
+    pub size: BigUint,
+    pub host_count: BigUint,
+    pub class: Option<char>,
+    pub special_purpose: Option<&'static str>,
+    pub reverse_zones: Vec<String>,
+    pub binary: String,
+    pub hex: String,
+}
+
+impl SubnetInfo {
+    ///  Gathers the report, usable hosts follow
+    ///  HostPolicy::Classic
+    pub fn new(ip: &IPAddress) -> SubnetInfo {
+        SubnetInfo::with_policy(ip, &HostPolicy::Classic)
+    }
+
+    pub fn with_policy(ip: &IPAddress, policy: &HostPolicy) -> SubnetInfo {
+        let broadcast = if ip.is_ipv4() {
+            Some(ip.broadcast())
+        } else {
+            None
+        };
+        SubnetInfo {
+            address: ip.clone(),
+            network: ip.network(),
+            netmask: ip.netmask(),
+            wildcard: ip.from(&ip.prefix.host_mask(), &ip.prefix),
+            broadcast,
+            first_host: ip.first_with(policy),
+            last_host: ip.last_with(policy),
+            size: ip.size(),
+            host_count: ip.host_count_with(policy),
+            class: classful(ip),
+            special_purpose: special_purpose(ip),
+            reverse_zones: ip.dns_rev_domains(),
+            binary: grouped_bits(ip),
+            hex: padded_hex(ip),
+        }
+    }
+
+    ///  Returns the report as lines of "Label: value"
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            ("Address", self.address.to_s()),
+            ("Network", self.network.to_string()),
+            ("Netmask", format!("{} = {}", self.netmask.to_s(), self.address.prefix.num)),
+            ("Wildcard", self.wildcard.to_s()),
+        ];
+        if let Some(ref broadcast) = self.broadcast {
+            lines.push(("Broadcast", broadcast.to_s()));
+        }
+        match (&self.first_host, &self.last_host) {
+            (Some(first), Some(last)) => {
+                lines.push(("HostMin", first.to_s()));
+                lines.push(("HostMax", last.to_s()));
+            }
+            _ => {
+                lines.push(("HostMin", String::from("-")));
+                lines.push(("HostMax", String::from("-")));
+            }
+        }
+        lines.push(("Hosts", format!("{}", self.host_count)));
+        if let Some(class) = self.class {
+            lines.push(("Class", class.to_string()));
+        }
+        lines.push(("Type", String::from(self.special_purpose.unwrap_or("Global Unicast"))));
+        for zone in self.reverse_zones.iter() {
+            lines.push(("Reverse", zone.clone()));
+        }
+        lines.push(("Binary", self.binary.clone()));
+        lines.push(("Hex", self.hex.clone()));
+
+        let mut ret = String::new();
+        for (label, value) in lines {
+            ret.push_str(&format!("{:<11}{}\n", format!("{}:", label), value));
+        }
+        ret
+    }
+
+    ///  Returns the report as a single JSON object, size and
+    ///  host_count are strings as IPv6 counts do not fit into
+    ///  the numbers of most JSON readers
+    pub fn to_json(&self) -> String {
+        let zones = self.reverse_zones.iter().map(|i| json_str(i)).collect::<Vec<_>>();
+        let fields = vec![
+            ("version", match self.address.ip_bits.version {
+                IpVersion::V4 => String::from("4"),
+                IpVersion::V6 => String::from("6"),
+                version => json_str(version.name()),
+            }),
+            ("address", json_str(&self.address.to_s())),
+            ("prefix", format!("{}", self.address.prefix.num)),
+            ("network", json_str(&self.network.to_string())),
+            ("netmask", json_str(&self.netmask.to_s())),
+            ("wildcard", json_str(&self.wildcard.to_s())),
+            ("broadcast", json_opt(self.broadcast.as_ref().map(|i| i.to_s()))),
+            ("first_host", json_opt(self.first_host.as_ref().map(|i| i.to_s()))),
+            ("last_host", json_opt(self.last_host.as_ref().map(|i| i.to_s()))),
+            ("size", json_str(&self.size.to_string())),
+            ("host_count", json_str(&self.host_count.to_string())),
+            ("class", json_opt(self.class.map(|i| i.to_string()))),
+            ("special_purpose", json_opt(self.special_purpose.map(String::from))),
+            ("reverse_zones", format!("[{}]", zones.join(","))),
+            ("binary", json_str(&self.binary)),
+            ("hex", json_str(&self.hex)),
+        ];
+        let fields = fields.iter()
+            .map(|&(key, ref value)| format!("{}:{}", json_str(key), value))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", fields.join(","))
+    }
+}
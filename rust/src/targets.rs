@@ -0,0 +1,315 @@
+//!  Nmap style target specifications.
+//!
+//!  A specification is a list of targets separated by
+//!  whitespace. A target is
+//!
+//!  * an address or network, 10.0.0.1, 10.0.0.0/24, 2001:db8::/120
+//!  * a range of addresses, 10.0.0.5-10.0.0.20
+//!  * an IPv4 octet pattern, every octet is a comma separated
+//!    list of numbers, ranges like 1-254, 10- or -10 and * for
+//!    0-255, optionally in brackets: 192.168.3-5,7.1,
+//!    10.0.0.1,5,9, 10.0.0.[1,5,9-12], 10.0.0.*
+//!
+//!  As in nmap a comma never separates targets, 10.0.0.1,5,9
+//!  are three hosts of 10.0.0.0/24. Targets prefixed with !
+//!  are excluded, as are the targets given to exclude (nmap
+//!  --exclude).
+//!
+//!    let targets = Targets::parse("10.0.0.1,5,9-12 !10.0.0.10").unwrap();
+//!    targets.hosts().map(|i| i.to_s()).collect::<Vec<_>>()
+//!      ///  ["10.0.0.1", "10.0.0.5", "10.0.0.9", "10.0.0.11", "10.0.0.12"]
+//!    targets.to_networks()
+//!      ///  [10.0.0.1/32, 10.0.0.5/32, 10.0.0.9/32, 10.0.0.11/32, 10.0.0.12/32]
+//!
+//!  hosts() is lazy, a /64 can be iterated without expanding
+//!  it. Hosts are returned in the order of the targets, an
+//!  address given twice is returned twice.
+
+use ip_bits::IpVersion;
+use ipaddress::IPAddress;
+use prefix::to_biguint;
+
+///  A single target, networks are stored as ranges
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Range { version: IpVersion, first: u128, last: u128 },
+    // the allowed values of every octet, sorted
+    Pattern([Vec<u8>; 4]),
+}
+
+// targets are parsed from IPv4 and IPv6 notation only
+fn to_ipaddress(version: IpVersion, addr: u128, prefix: usize) -> IPAddress {
+    IPAddress::from_u128(version, addr, prefix).unwrap()
+}
+
+fn parse_octet_value(s: &str, target: &str) -> Result<u8, String> {
+    s.parse::<u8>().map_err(|_| format!("Invalid target {}", target))
+}
+
+// a list of numbers, ranges with optional ends and *,
+// optionally in brackets
+fn parse_octet(s: &str, target: &str) -> Result<Vec<u8>, String> {
+    let list = match s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+        Some(list) => list,
+        None => s,
+    };
+    let mut ret = Vec::new();
+    for item in list.split(',') {
+        let item = if item == "*" { "0-255" } else { item };
+        let (from, to) = match item.find('-') {
+            Some(pos) => {
+                let from = &item[..pos];
+                let to = &item[pos + 1..];
+                (if from.is_empty() { 0 } else { parse_octet_value(from, target)? },
+                 if to.is_empty() { 255 } else { parse_octet_value(to, target)? })
+            }
+            None => {
+                let value = parse_octet_value(item, target)?;
+                (value, value)
+            }
+        };
+        if from > to {
+            return Err(format!("Invalid target {}", target));
+        }
+        ret.extend(from..=to);
+    }
+    ret.sort();
+    ret.dedup();
+    Ok(ret)
+}
+
+impl Target {
+    pub fn parse(s: &str) -> Result<Target, String> {
+        let parts: Vec<&str> = s.split('-').collect();
+        if parts.len() == 2 && parts.iter().all(|p| p.contains(':') || p.matches('.').count() == 3) {
+            let first = IPAddress::parse(parts[0])?;
+            let last = IPAddress::parse(parts[1])?;
+            if first.ip_bits.version != last.ip_bits.version || first.to_u128() > last.to_u128() {
+                return Err(format!("Invalid range {}", s));
+            }
+            return Ok(Target::Range { version: first.ip_bits.version, first: first.to_u128(), last: last.to_u128() });
+        }
+        if s.contains(&['*', '-', '[', ','][..]) {
+            let octets: Vec<&str> = s.split('.').collect();
+            if octets.len() != 4 {
+                return Err(format!("Invalid target {}", s));
+            }
+            return Ok(Target::Pattern([
+                parse_octet(octets[0], s)?,
+                parse_octet(octets[1], s)?,
+                parse_octet(octets[2], s)?,
+                parse_octet(octets[3], s)?,
+            ]));
+        }
+        let net = IPAddress::parse(s)?;
+        Ok(Target::Range {
+            version: net.ip_bits.version,
+            first: net.network().to_u128(),
+            last: net.broadcast().to_u128(),
+        })
+    }
+
+    pub fn contains(&self, ip: &IPAddress) -> bool {
+        match *self {
+            Target::Range { version, first, last } => {
+                ip.ip_bits.version == version && first <= ip.to_u128() && ip.to_u128() <= last
+            }
+            Target::Pattern(ref octets) => {
+                ip.is_ipv4() && ip.octets().iter().zip(octets.iter()).all(|(o, values)| values.binary_search(o).is_ok())
+            }
+        }
+    }
+
+    // the +pos+th address of the target
+    fn nth(&self, pos: u128) -> Option<(IpVersion, u128)> {
+        match *self {
+            Target::Range { version, first, last } => {
+                if pos > last - first {
+                    return None;
+                }
+                Some((version, first + pos))
+            }
+            Target::Pattern(ref octets) => {
+                let mut rest = pos;
+                let mut addr = 0u128;
+                for (i, values) in octets.iter().enumerate().rev() {
+                    let len = values.len() as u128;
+                    addr |= (values[(rest % len) as usize] as u128) << (8 * (3 - i));
+                    rest /= len;
+                }
+                if rest > 0 {
+                    return None;
+                }
+                Some((IpVersion::V4, addr))
+            }
+        }
+    }
+
+    ///  The smallest list of networks which covers the target
+    pub fn to_networks(&self) -> Vec<IPAddress> {
+        match *self {
+            Target::Range { version, first, last } => {
+                to_ipaddress(version, 0, 0).range_to_networks(&to_biguint(first), &to_biguint(last))
+            }
+            Target::Pattern(ref octets) => {
+                // octets behind the last restricted one are
+                // host bits, the ones in front are enumerated
+                let k = octets.iter().rposition(|values| values.len() < 256).unwrap_or(0);
+                let shift = 8 * (3 - k);
+                let template = to_ipaddress(IpVersion::V4, 0, 0);
+                let mut ret = Vec::new();
+                let mut idx = [0usize; 4];
+                loop {
+                    let mut prefix = 0u128;
+                    for i in 0..k {
+                        prefix |= (octets[i][idx[i]] as u128) << (8 * (3 - i));
+                    }
+                    for run in runs(&octets[k]) {
+                        let first = prefix | (run.0 as u128) << shift;
+                        let last = prefix | (run.1 as u128) << shift | ((1u128 << shift) - 1);
+                        ret.extend(template.range_to_networks(&to_biguint(first), &to_biguint(last)));
+                    }
+                    // odometer over the octets in front of k
+                    let mut i = k;
+                    loop {
+                        if i == 0 {
+                            return ret;
+                        }
+                        i -= 1;
+                        idx[i] += 1;
+                        if idx[i] < octets[i].len() {
+                            break;
+                        }
+                        idx[i] = 0;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// the runs of consecutive values of a sorted list
+fn runs(values: &[u8]) -> Vec<(u8, u8)> {
+    let mut ret: Vec<(u8, u8)> = Vec::new();
+    for &v in values {
+        match ret.last_mut() {
+            Some(last) if last.1 as u16 + 1 == v as u16 => last.1 = v,
+            _ => ret.push((v, v)),
+        }
+    }
+    ret
+}
+
+///  Parsed targets and exclusions
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Targets {
+    pub include: Vec<Target>,
+    pub exclude: Vec<Target>,
+}
+
+impl Targets {
+    pub fn parse(spec: &str) -> Result<Targets, String> {
+        let mut ret = Targets::default();
+        for token in spec.split_whitespace() {
+            match token.strip_prefix('!') {
+                Some(exclude) => ret.exclude.push(Target::parse(exclude)?),
+                None => ret.include.push(Target::parse(token)?),
+            }
+        }
+        Ok(ret)
+    }
+
+    ///  Returns the targets with everything in +spec+ excluded
+    pub fn exclude(&self, spec: &str) -> Result<Targets, String> {
+        let mut ret = self.clone();
+        for token in spec.split_whitespace() {
+            ret.exclude.push(Target::parse(token)?);
+        }
+        Ok(ret)
+    }
+
+    pub fn is_excluded(&self, ip: &IPAddress) -> bool {
+        self.exclude.iter().any(|i| i.contains(ip))
+    }
+
+    ///  Iterates over the included hosts which are not
+    ///  excluded, as /32 or /128 addresses
+    pub fn hosts(&self) -> Hosts<'_> {
+        Hosts { targets: self, item: 0, pos: 0 }
+    }
+
+    ///  The included addresses as sorted list of networks,
+    ///  exclusions removed and adjacent networks merged
+    pub fn to_networks(&self) -> Vec<IPAddress> {
+        let exclude: Vec<IPAddress> = self.exclude.iter().flat_map(|i| i.to_networks()).collect();
+        let networks: Vec<IPAddress> = self.include.iter()
+            .flat_map(|i| i.to_networks())
+            .flat_map(|i| i.exclude(&exclude))
+            .collect();
+        let mut ret = IPAddress::summarize(&networks);
+        ret.sort();
+        ret
+    }
+
+    ///  The included addresses as sorted list of first and
+    ///  last address of each continuous range
+    pub fn to_ranges(&self) -> Vec<(IPAddress, IPAddress)> {
+        let mut ret: Vec<(IPAddress, IPAddress)> = Vec::new();
+        for net in self.to_networks() {
+            let first = net.network().change_prefix(net.ip_bits.bits).unwrap();
+            let last = net.broadcast().change_prefix(net.ip_bits.bits).unwrap();
+            if let Some(prev) = ret.last_mut() {
+                if prev.1.ip_bits.version == first.ip_bits.version
+                    && prev.1.to_u128().checked_add(1) == Some(first.to_u128())
+                {
+                    prev.1 = last;
+                    continue;
+                }
+            }
+            ret.push((first, last));
+        }
+        ret
+    }
+}
+
+///  Lazy iterator over the hosts of Targets
+pub struct Hosts<'a> {
+    targets: &'a Targets,
+    item: usize,
+    pos: u128,
+}
+
+impl<'a> Iterator for Hosts<'a> {
+    type Item = IPAddress;
+    fn next(&mut self) -> Option<IPAddress> {
+        loop {
+            let target = self.targets.include.get(self.item)?;
+            let (version, addr) = match target.nth(self.pos) {
+                Some(i) => i,
+                None => {
+                    self.item += 1;
+                    self.pos = 0;
+                    continue;
+                }
+            };
+            let ip = to_ipaddress(version, addr, version.bits());
+            // the last position to skip, ranges excluded from a
+            // range are jumped over in one step
+            let excluded = self.targets.exclude.iter().find(|i| i.contains(&ip));
+            let skip_to = excluded.map(|exclude| match (target, exclude) {
+                (&Target::Range { first, .. }, &Target::Range { last, .. }) => last - first,
+                _ => self.pos,
+            });
+            match skip_to.unwrap_or(self.pos).checked_add(1) {
+                Some(pos) => self.pos = pos,
+                None => {
+                    self.item += 1;
+                    self.pos = 0;
+                }
+            }
+            if skip_to.is_none() {
+                return Some(ip);
+            }
+        }
+    }
+}
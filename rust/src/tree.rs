@@ -0,0 +1,181 @@
+//!  Nesting hierarchy of a flat list of networks.
+//!
+//!  Every network becomes a node below the smallest other
+//!  network which includes it, networks without a parent are
+//!  the roots of the forest.
+//!
+//!    let forest = Forest::with_gaps(&IPAddress::to_ipaddress_vec(
+//!        vec!["10.0.0.0/16", "10.0.0.0/20", "10.0.1.0/24", "10.0.16.0/20"]).unwrap());
+//!
+//!    print!("{}", forest.to_text());
+//!      ///  10.0.0.0/16 (12.50% used)
+//!      ///    10.0.0.0/20 (6.25% used)
+//!      ///      10.0.0.0/24 free
+//!      ///      10.0.1.0/24
+//!      ///      10.0.2.0/23 free
+//!      ///      10.0.4.0/22 free
+//!      ///      10.0.8.0/21 free
+//!      ///    10.0.16.0/20
+//!      ///    10.0.32.0/19 free
+//!      ///    10.0.64.0/18 free
+//!      ///    10.0.128.0/17 free
+
+use ipaddress::IPAddress;
+use num::bigint::BigUint;
+use num_traits::cast::ToPrimitive;
+use num_traits::identities::Zero;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub network: IPAddress,
+    ///  true for synthesized nodes of unused space
+    pub gap: bool,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    fn new(network: IPAddress, gap: bool) -> Node {
+        Node {
+            network,
+            gap,
+            children: Vec::new(),
+        }
+    }
+
+    ///  Returns the number of addresses of the node which are
+    ///  covered by child networks, gaps do not count
+    pub fn covered(&self) -> BigUint {
+        self.children.iter().filter(|i| !i.gap).fold(BigUint::zero(), |acc, i| acc + i.network.size())
+    }
+
+    ///  Returns the number of addresses of the node which are
+    ///  not covered by any child network
+    pub fn free(&self) -> BigUint {
+        self.network.size() - self.covered()
+    }
+
+    ///  Returns the covered part of the node in percent, a
+    ///  leaf is not subdivided and has 0.0
+    pub fn utilization(&self) -> f64 {
+        self.covered().to_f64().unwrap() * 100.0 / self.network.size().to_f64().unwrap()
+    }
+
+    fn walk<'a>(&'a self, depth: usize, ret: &mut Vec<(usize, &'a Node)>) {
+        ret.push((depth, self));
+        for child in self.children.iter() {
+            child.walk(depth + 1, ret);
+        }
+    }
+
+    fn add_gaps(&mut self) {
+        for child in self.children.iter_mut() {
+            child.add_gaps();
+        }
+        if self.children.is_empty() {
+            return;
+        }
+        let used = self.children.iter().map(|i| i.network.clone()).collect::<Vec<_>>();
+        for free in self.network.exclude(&used) {
+            self.children.push(Node::new(free, true));
+        }
+        self.children.sort_by(|a, b| a.network.cmp(&b.network));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Forest {
+    pub roots: Vec<Node>,
+}
+
+impl Forest {
+    ///  Builds the forest of the networks of the given
+    ///  addresses, duplicates are dropped. Siblings are sorted.
+    pub fn new(networks: &[IPAddress]) -> Forest {
+        let mut networks = networks.iter().map(|i| i.network()).collect::<Vec<_>>();
+        networks.sort();
+        networks.dedup();
+
+        // the stack holds the path from a root to the last node
+        let mut roots = Vec::new();
+        let mut stack: Vec<Node> = Vec::new();
+        for network in networks {
+            while stack.last().map_or(false, |top| !top.network.includes(&network)) {
+                Forest::close(&mut stack, &mut roots);
+            }
+            stack.push(Node::new(network, false));
+        }
+        while !stack.is_empty() {
+            Forest::close(&mut stack, &mut roots);
+        }
+        Forest { roots }
+    }
+
+    ///  Like new, but every subdivided node also gets the free
+    ///  space between its children as gap nodes
+    pub fn with_gaps(networks: &[IPAddress]) -> Forest {
+        let mut ret = Forest::new(networks);
+        for root in ret.roots.iter_mut() {
+            root.add_gaps();
+        }
+        ret
+    }
+
+    fn close(stack: &mut Vec<Node>, roots: &mut Vec<Node>) {
+        let node = stack.pop().unwrap();
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+
+    ///  Returns all nodes depth first together with their
+    ///  depth, roots have depth 0
+    pub fn iter(&self) -> Vec<(usize, &Node)> {
+        let mut ret = Vec::new();
+        for root in self.roots.iter() {
+            root.walk(0, &mut ret);
+        }
+        ret
+    }
+
+    ///  Returns the nodes without children
+    pub fn leaves(&self) -> Vec<&Node> {
+        self.iter().into_iter().map(|(_, i)| i).filter(|i| i.children.is_empty()).collect()
+    }
+
+    ///  Returns the nodes from the root down to the most
+    ///  specific node which includes +ip+, empty if no root
+    ///  includes it
+    pub fn path(&self, ip: &IPAddress) -> Vec<&Node> {
+        let mut ret = Vec::new();
+        let mut level = &self.roots;
+        while let Some(node) = level.iter().find(|i| i.network.includes(ip)) {
+            ret.push(node);
+            level = &node.children;
+        }
+        ret
+    }
+
+    ///  Returns the most specific node which includes +ip+
+    pub fn find(&self, ip: &IPAddress) -> Option<&Node> {
+        self.path(ip).pop()
+    }
+
+    ///  Renders the forest indented by two spaces per level.
+    ///  Subdivided nodes show their utilization, gaps are
+    ///  marked as free.
+    pub fn to_text(&self) -> String {
+        let mut ret = String::new();
+        for (depth, node) in self.iter() {
+            let note = if node.gap {
+                String::from(" free")
+            } else if !node.children.is_empty() {
+                format!(" ({:.2}% used)", node.utilization())
+            } else {
+                String::new()
+            };
+            ret.push_str(&format!("{}{}{}\n", "  ".repeat(depth), node.network.to_string(), note));
+        }
+        ret
+    }
+}
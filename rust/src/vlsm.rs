@@ -0,0 +1,128 @@
+//!  Variable length subnet masking.
+//!
+//!  Given a parent network and a list of named host count
+//!  requirements, the planner picks the smallest prefix for
+//!  every requirement and places the subnets largest first,
+//!  so that no space is lost to alignment.
+//!
+//!    let plan = vlsm::plan(&IPAddress::parse("10.20.0.0/20").unwrap(),
+//!                          &[Requirement::new("a", 500),
+//!                            Requirement::new("b", 120)]).unwrap();
+//!
+//!    plan.assignments
+//!      ///  a => 10.20.0.0/23, b => 10.20.2.0/25
+//!    plan.free
+//!      ///  ["10.20.2.128/25", "10.20.3.0/24", "10.20.4.0/22", "10.20.8.0/21"]
+
+use ipaddress::IPAddress;
+use host_policy::HostPolicy;
+use ipam::{Allocator, Policy};
+use num::bigint::BigUint;
+use num_traits::identities::{One, Zero};
+use num_traits::FromPrimitive;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    pub name: String,
+    pub hosts: BigUint,
+}
+
+impl Requirement {
+    pub fn new<S: Into<String>>(name: S, hosts: usize) -> Requirement {
+        Requirement {
+            name: name.into(),
+            hosts: BigUint::from_usize(hosts).unwrap(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub name: String,
+    pub hosts: BigUint,
+    pub network: IPAddress,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    pub assignments: Vec<Assignment>,
+    pub free: Vec<IPAddress>,
+}
+
+///  Returns the largest prefix of the family of +parent+ which
+///  still offers +hosts+ usable addresses. The network and
+///  broadcast addresses of IPv4 are not usable, see
+///  HostPolicy::Classic.
+///
+///    let parent = IPAddress::parse("10.0.0.0/8").unwrap();
+///    vlsm::prefix_for_hosts(&parent, &BigUint::from_u32(60).unwrap())
+///      ///  Ok(26)
+///
+pub fn prefix_for_hosts(parent: &IPAddress, hosts: &BigUint) -> Result<usize, String> {
+    prefix_for_hosts_with(parent, hosts, &HostPolicy::Classic)
+}
+
+pub fn prefix_for_hosts_with(parent: &IPAddress, hosts: &BigUint, policy: &HostPolicy)
+                             -> Result<usize, String> {
+    if hosts.is_zero() {
+        return Err(String::from("a subnet needs at least one host"));
+    }
+    let mut prefix = parent.ip_bits.bits;
+    loop {
+        let candidate = parent.change_prefix(prefix).unwrap();
+        if policy.host_count(&candidate) >= *hosts {
+            return Ok(prefix);
+        }
+        if prefix == 0 {
+            return Err(format!("{} hosts do not fit into the address family", hosts));
+        }
+        prefix -= 1;
+    }
+}
+
+///  Places all requirements inside +parent+. The assignments
+///  are returned in placement order, which is largest first
+///  and keeps the given order for equal sizes.
+pub fn plan(parent: &IPAddress, requirements: &[Requirement]) -> Result<Plan, String> {
+    plan_with(parent, requirements, &HostPolicy::Classic)
+}
+
+///  Like plan, but sizes the subnets so that they offer the
+///  required number of hosts under the given HostPolicy.
+///
+///    vlsm::plan_with(&parent, &reqs, &HostPolicy::Reserved { head: 4, tail: 1 })
+///
+pub fn plan_with(parent: &IPAddress, requirements: &[Requirement], policy: &HostPolicy)
+                 -> Result<Plan, String> {
+    let mut sized = Vec::new();
+    for req in requirements {
+        let prefix = prefix_for_hosts_with(parent, &req.hosts, policy)
+            .map_err(|e| format!("{}: {}", req.name, e))?;
+        sized.push((prefix, req));
+    }
+    sized.sort_by_key(|&(prefix, _)| prefix);
+
+    let needed = sized.iter()
+        .fold(BigUint::zero(), |acc, &(prefix, _)| acc + (BigUint::one() << (parent.ip_bits.bits - prefix)));
+    let mut ipam = Allocator::new();
+    ipam.add_pool(parent)?;
+    let pool = parent.network();
+    let mut assignments = Vec::new();
+    for (prefix, req) in sized {
+        match ipam.allocate_from(&pool, prefix, Policy::FirstFit) {
+            Ok(network) => assignments.push(Assignment {
+                name: req.name.clone(),
+                hosts: req.hosts.clone(),
+                network,
+            }),
+            Err(_) => {
+                return Err(format!("{} needs a /{} which does not fit into {}: {} addresses required, {} available",
+                                   req.name, prefix, pool.to_string(), needed, pool.size()));
+            }
+        }
+    }
+    Ok(Plan {
+        assignments,
+        free: ipam.free_blocks(&pool)?,
+    })
+}
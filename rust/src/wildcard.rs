@@ -0,0 +1,173 @@
+//!  Cisco style wildcard masks.
+//!
+//!  ACLs and OSPF write a network as an address and a wildcard
+//!  mask, a set bit in the mask means the address bit does not
+//!  matter. Contiguous wildcards are CIDR networks written the
+//!  other way round, non contiguous ones like 0.255.0.255 match
+//!  patterns which need several networks to express.
+//!
+//!    let acl = Wildcard::parse("10.0.0.0 0.255.0.255").unwrap();
+//!    acl.matches(&IPAddress::parse("10.20.0.30").unwrap())
+//!      ///  true
+//!    acl.is_contiguous()
+//!      ///  false
+//!    acl.network_count()
+//!      ///  256
+//!    acl.to_networks()[1]
+//!      ///  10.1.0.0/24
+
+use core::fmt;
+use core::str::FromStr;
+
+use ip_bits::IpVersion;
+use ipaddress::IPAddress;
+
+///  An address pattern, +address+ has all wildcard bits
+///  cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wildcard {
+    pub version: IpVersion,
+    pub address: u128,
+    pub wildcard: u128,
+}
+
+// Wildcard::new accepts IPv4 and IPv6 only
+fn to_ipaddress(version: IpVersion, addr: u128, prefix: usize) -> IPAddress {
+    IPAddress::from_u128(version, addr, prefix).unwrap()
+}
+
+impl Wildcard {
+    ///  Builds the pattern of +address+ and the wildcard mask
+    ///  +wildcard+, both of the same family. Prefixes are
+    ///  ignored.
+    pub fn new(address: &IPAddress, wildcard: &IPAddress) -> Result<Wildcard, String> {
+        if !address.is_ipv4() && !address.is_ipv6() {
+            return Err(format!("{} is not an IP address", address.to_s()));
+        }
+        if address.ip_bits.version != wildcard.ip_bits.version {
+            return Err(format!("{} and {} are of different families", address.to_s(), wildcard.to_s()));
+        }
+        let wildcard = wildcard.to_u128();
+        Ok(Wildcard {
+            version: address.ip_bits.version,
+            address: address.to_u128() & !wildcard,
+            wildcard,
+        })
+    }
+
+    ///  Parses the ACL forms "10.0.0.0 0.0.0.255",
+    ///  "host 10.0.0.1" and "any" (IPv4)
+    pub fn parse(s: &str) -> Result<Wildcard, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["any"] => Wildcard::new(&ip!("0.0.0.0"), &ip!("255.255.255.255")),
+            ["host", addr] => {
+                let host = IPAddress::parse(*addr)?;
+                if host.prefix.num != host.ip_bits.bits {
+                    return Err(format!("host needs a single address, got {}", host.to_string()));
+                }
+                Wildcard::from_network(&host)
+            }
+            [addr, wildcard] => Wildcard::new(&IPAddress::parse(*addr)?, &IPAddress::parse(*wildcard)?),
+            _ => Err(format!("Invalid wildcard {}", s)),
+        }
+    }
+
+    ///  The pattern which matches the network +net+
+    ///
+    ///    Wildcard::from_network(&IPAddress("10.0.0.0/24")).to_string()
+    ///      ///  "10.0.0.0 0.0.0.255"
+    ///
+    pub fn from_network(net: &IPAddress) -> Result<Wildcard, String> {
+        let host_mask = net.prefix.host_mask_u128();
+        let wildcard = IPAddress::from_u128(net.ip_bits.version, host_mask, net.ip_bits.bits)?;
+        Wildcard::new(net, &wildcard)
+    }
+
+    fn bits(&self) -> usize {
+        self.version.bits()
+    }
+
+    pub fn address(&self) -> IPAddress {
+        to_ipaddress(self.version, self.address, self.bits())
+    }
+
+    pub fn wildcard(&self) -> IPAddress {
+        to_ipaddress(self.version, self.wildcard, self.bits())
+    }
+
+    ///  True if the wildcard bits are all at the end, the
+    ///  pattern is then a single network
+    pub fn is_contiguous(&self) -> bool {
+        self.wildcard & self.wildcard.wrapping_add(1) == 0
+    }
+
+    ///  The network of a contiguous pattern
+    ///
+    ///    Wildcard::parse("10.0.0.0 0.0.0.255").to_network()
+    ///      ///  Some(10.0.0.0/24)
+    ///
+    pub fn to_network(&self) -> Option<IPAddress> {
+        if !self.is_contiguous() {
+            return None;
+        }
+        Some(to_ipaddress(self.version, self.address, self.bits() - self.trailing()))
+    }
+
+    ///  True if +ip+ is of the same family and agrees with the
+    ///  address on every bit which is not a wildcard
+    pub fn matches(&self, ip: &IPAddress) -> bool {
+        ip.ip_bits.version == self.version && (ip.to_u128() ^ self.address) & !self.wildcard == 0
+    }
+
+    // number of wildcard bits at the end, they make up the
+    // host part of every network of the expansion
+    fn trailing(&self) -> usize {
+        (!self.wildcard).trailing_zeros() as usize
+    }
+
+    // the wildcard bits in front of the trailing ones
+    fn scattered(&self) -> u128 {
+        self.wildcard & (!0u128).checked_shl(self.trailing() as u32).unwrap_or(0)
+    }
+
+    ///  How many networks to_networks returns, check it before
+    ///  expanding wide patterns
+    pub fn network_count(&self) -> u128 {
+        1u128 << self.scattered().count_ones()
+    }
+
+    ///  Expands the pattern into the sorted list of networks
+    ///  which match exactly the same addresses
+    ///
+    ///    Wildcard::parse("10.0.0.0 0.0.1.3").to_networks()
+    ///      ///  [10.0.0.0/30, 10.0.1.0/30]
+    ///
+    pub fn to_networks(&self) -> Vec<IPAddress> {
+        let prefix = self.bits() - self.trailing();
+        let scattered = self.scattered();
+        let mut ret = Vec::new();
+        let mut set = 0u128;
+        loop {
+            ret.push(to_ipaddress(self.version, self.address | set, prefix));
+            if set == scattered {
+                return ret;
+            }
+            // next subset of the scattered bits in ascending order
+            set = set.wrapping_sub(scattered) & scattered;
+        }
+    }
+}
+
+impl fmt::Display for Wildcard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.address().to_s(), self.wildcard().to_s())
+    }
+}
+
+impl FromStr for Wildcard {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Wildcard, String> {
+        Wildcard::parse(s)
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::fs::File;
+    use std::io::Write;
+    use std::process::{Command, Output, Stdio};
+
+    fn ipaddress(args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_ipaddress")).args(args).output().unwrap()
+    }
+
+    fn stdout(args: &[&str]) -> String {
+        let out = ipaddress(args);
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        String::from_utf8(out.stdout).unwrap()
+    }
+
+    // a temporary list file, removed when dropped
+    struct ListFile {
+        path: String,
+    }
+
+    impl Drop for ListFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+
+    fn list_file(name: &str, content: &str) -> ListFile {
+        let path = env::temp_dir().join(format!("ipaddress-test-{}-{}", std::process::id(), name));
+        File::create(&path).unwrap().write_all(content.as_bytes()).unwrap();
+        ListFile { path: path.to_str().unwrap().to_string() }
+    }
+
+    fn lines_with(out: &str, label: &str) -> Vec<String> {
+        out.lines().filter(|i| i.starts_with(label)).map(|i| i[11..].to_string()).collect()
+    }
+
+    #[test]
+    pub fn test_report() {
+        let out = stdout(&["192.168.1.10/24", "2001:db8::1/64"]);
+        assert_eq!(lines_with(&out, "Network:"), ["192.168.1.0/24", "2001:db8::/64"]);
+        assert_eq!(lines_with(&out, "Netmask:"), ["255.255.255.0 = 24", "ffff:ffff:ffff:ffff:: = 64"]);
+        assert_eq!(lines_with(&out, "Wildcard:"), ["0.0.0.255", "::ffff:ffff:ffff:ffff"]);
+        assert_eq!(lines_with(&out, "Broadcast:"), ["192.168.1.255"]);
+        assert_eq!(lines_with(&out, "HostMin:"), ["192.168.1.1", "2001:db8::"]);
+        assert_eq!(lines_with(&out, "HostMax:"), ["192.168.1.254", "2001:db8::ffff:ffff:ffff:ffff"]);
+        assert_eq!(lines_with(&out, "Hosts:"), ["254", "18446744073709551616"]);
+        assert_eq!(lines_with(&out, "Reverse:"),
+                   ["1.168.192.in-addr.arpa", "0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa"]);
+    }
+
+    #[test]
+    pub fn test_split_subnet_supernet() {
+        let out = stdout(&["--split", "3", "172.16.10.0/24"]);
+        assert_eq!(lines_with(&out, "Network:"), ["172.16.10.0/26", "172.16.10.64/26", "172.16.10.128/25"]);
+        let out = stdout(&["--subnet", "/26", "172.16.10.0/24"]);
+        assert_eq!(lines_with(&out, "Network:").len(), 4);
+        let out = stdout(&["172.16.10.0/24", "--supernet", "22"]);
+        assert_eq!(lines_with(&out, "Network:"), ["172.16.8.0/22"]);
+    }
+
+    #[test]
+    pub fn test_json() {
+        let out = stdout(&["--json", "10.0.0.1/8", "::1"]);
+        assert!(out.starts_with("[{\"version\":4,\"address\":\"10.0.0.1\",\"prefix\":8,"));
+        assert!(out.contains("},{\"version\":6,\"address\":\"::1\",\"prefix\":128,"));
+        assert!(out.ends_with("}]\n"));
+    }
+
+    #[test]
+    pub fn test_errors() {
+        for args in [vec![], vec!["10.0.0.256"], vec!["--bogus", "10.0.0.0/8"],
+                     vec!["--split", "3", "--subnet", "/26", "10.0.0.0/24"],
+                     vec!["--supernet", "/26", "10.0.0.0/24"], vec!["--split"]].iter() {
+            let out = ipaddress(args);
+            assert!(!out.status.success());
+            assert!(String::from_utf8(out.stderr).unwrap().starts_with("ipaddress: "));
+        }
+        let out = stdout(&["--help"]);
+        assert!(out.starts_with("usage: ipaddress"));
+    }
+
+    #[test]
+    pub fn test_list_commands() {
+        let a = list_file("a", "# ours\n10.0.0.0/24\n10.0.1.0/24 # second\n\n192.168.0.0/16\n10.0.0.0/24\n");
+        let b = list_file("b", "10.0.0.128/25\n192.168.10.0/24\n2001:db8::/32\n");
+        assert_eq!(stdout(&["aggregate", &a.path]), "10.0.0.0/23\n192.168.0.0/16\n");
+        assert_eq!(stdout(&["union", &a.path, &b.path, "--json"]),
+                   "[\"10.0.0.0/23\",\"192.168.0.0/16\",\"2001:db8::/32\"]\n");
+        assert_eq!(stdout(&["intersect", &a.path, &b.path]), "10.0.0.128/25\n192.168.10.0/24\n");
+        assert_eq!(stdout(&["subtract", &a.path, &b.path]),
+                   "10.0.0.0/25\n10.0.1.0/24\n\
+                    192.168.0.0/21\n192.168.8.0/23\n192.168.11.0/24\n192.168.12.0/22\n\
+                    192.168.16.0/20\n192.168.32.0/19\n192.168.64.0/18\n192.168.128.0/17\n");
+        assert_eq!(stdout(&["dedupe", &a.path]), "10.0.0.0/24\n10.0.1.0/24\n192.168.0.0/16\n");
+        assert_eq!(stdout(&["sort", &b.path, &a.path]).lines().next(), Some("10.0.0.0/24"));
+        assert_eq!(stdout(&["union", &b.path, "--format", "cisco", "--name", "X"]),
+                   "ip prefix-list X seq 5 permit 10.0.0.128/25\n\
+                    ip prefix-list X seq 10 permit 192.168.10.0/24\n\
+                    ipv6 prefix-list X seq 5 permit 2001:db8::/32\n");
+        assert_eq!(stdout(&["dedupe", &b.path, "--format", "juniper"]).lines().last(),
+                   Some("set policy-options prefix-list PL 2001:db8::/32"));
+
+        assert_eq!(stdout(&["contains", "10.0.1.5", &a.path, &b.path]), "10.0.1.0/24\n");
+        assert!(!ipaddress(&["contains", "1.1.1.1", &a.path]).status.success());
+    }
+
+    #[test]
+    pub fn test_list_stdin() {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ipaddress"))
+            .args(["aggregate"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(b"10.0.0.0/25\n10.0.0.128/25\n").unwrap();
+        let out = child.wait_with_output().unwrap();
+        assert_eq!(String::from_utf8(out.stdout).unwrap(), "10.0.0.0/24\n");
+    }
+
+    #[test]
+    pub fn test_list_errors() {
+        let bad = list_file("bad", "10.0.0.0/24\nfoo\n# fine\n10.0.0.300\n");
+        let out = ipaddress(&["aggregate", &bad.path]);
+        assert!(!out.status.success());
+        let err = String::from_utf8(out.stderr).unwrap();
+        assert!(err.contains(&format!("{}:2: ", bad.path)));
+        assert!(err.contains(&format!("{}:4: ", bad.path)));
+        assert!(!ipaddress(&["intersect", &bad.path]).status.success());
+        assert!(!ipaddress(&["contains", &bad.path]).status.success());
+        assert!(!ipaddress(&["sort", "--format", "xml", &bad.path]).status.success());
+    }
+}
@@ -0,0 +1,42 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::covering;
+
+    fn parse(nets: Vec<&str>) -> Vec<IPAddress> {
+        IPAddress::to_ipaddress_vec(nets).unwrap()
+    }
+
+    #[test]
+    pub fn test_covering_supernet() {
+        let nets = parse(vec!["10.0.0.1", "10.0.0.6"]);
+        assert_eq!(IPAddress::to_string_vec(&IPAddress::covering_supernet(&nets)), ["10.0.0.0/29"]);
+        let nets = parse(vec!["192.168.1.0/24", "192.168.2.77/24", "2001:db8:1::1", "2001:db8:ff::/48",
+                              "10.0.0.0/8"]);
+        assert_eq!(IPAddress::to_string_vec(&IPAddress::covering_supernet(&nets)),
+                   ["0.0.0.0/0", "2001:db8::/40"]);
+        let nets = parse(vec!["172.16.5.0/24"]);
+        assert_eq!(IPAddress::to_string_vec(&IPAddress::covering_supernet(&nets)), ["172.16.5.0/24"]);
+        assert_eq!(IPAddress::covering_supernet(&[]).len(), 0);
+    }
+
+    #[test]
+    pub fn test_report() {
+        let report = covering::report(&parse(vec!["10.0.0.1", "10.0.0.6", "10.0.0.7/32"]));
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].network.to_string(), "10.0.0.0/29");
+        assert_eq!(IPAddress::to_string_vec(&report[0].aggregate), ["10.0.0.1/32", "10.0.0.6/31"]);
+        assert_eq!(report[0].covered.to_string(), "3");
+        assert_eq!(report[0].extra.to_string(), "5");
+
+        let report = covering::report(&parse(vec!["10.0.0.0/25", "10.0.0.128/25", "::1"]));
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].network.to_string(), "10.0.0.0/24");
+        assert_eq!(report[0].extra.to_string(), "0");
+        assert_eq!(report[1].network.to_string(), "::1/128");
+        assert_eq!(report[1].covered.to_string(), "1");
+    }
+}
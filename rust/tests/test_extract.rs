@@ -0,0 +1,69 @@
+extern crate ipaddress;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::extract::{self, Options};
+
+    fn found(text: &str) -> Vec<String> {
+        extract::extract(text).map(|m| m.address.to_string()).collect()
+    }
+
+    fn spans(text: &str, options: Options) -> Vec<&str> {
+        extract::extract_with(text, options).map(|m| m.as_str(text)).collect()
+    }
+
+    #[test]
+    pub fn test_ipv4() {
+        let text = "deny 10.0.0.0/8 from 192.168.1.7, ok.";
+        let matches: Vec<_> = extract::extract(text).collect();
+        assert_eq!(matches.len(), 2);
+        assert_eq!((matches[0].start, matches[0].end), (5, 15));
+        assert_eq!(matches[0].address.to_string(), "10.0.0.0/8");
+        assert_eq!(matches[1].as_str(text), "192.168.1.7");
+        assert_eq!(found("foobar172.16.10.1barbaz"), ["172.16.10.1/32"]);
+        assert_eq!(found("at 10.0.0.1. Then 10.0.0.2:8080 and (10.0.0.3)"), ["10.0.0.1/32", "10.0.0.2/32", "10.0.0.3/32"]);
+        assert_eq!(found("10.0.0.1,10.0.0.2;10.0.0.3"), ["10.0.0.1/32", "10.0.0.2/32", "10.0.0.3/32"]);
+    }
+
+    #[test]
+    pub fn test_boundaries() {
+        assert!(found("version 1.2.3.4.5 and 0.1.2.3.4").is_empty());
+        assert!(found("v.1.2.3.4 2.3.4").is_empty());
+        assert!(found("256.1.1.1 1.2.3.4444").is_empty());
+        assert!(found("std::vector at 12:30:45 in foo::bar()").is_empty());
+        assert!(found("a :: b").is_empty());
+        assert!(found("foo :: Int -> Int").is_empty());
+        assert!(found("x\t::\ny").is_empty());
+        assert_eq!(found("any (::) or ::/0 on ::1"), ["::/128", "::/0", "::1/128"]);
+        assert_eq!(found("10.0.0.0/33 10.0.0.0/8x"), ["10.0.0.0/32", "10.0.0.0/32"]);
+        assert_eq!(found("ping 10.0.0.1... done"), ["10.0.0.1/32"]);
+        assert_eq!(found("...1.2.3.4 and 5.6.7.8.."), ["1.2.3.4/32", "5.6.7.8/32"]);
+        assert!(found("1.2.3.4.x and 1.2.3.4.a1").is_empty());
+    }
+
+    #[test]
+    pub fn test_ipv6() {
+        let text = "connect [2001:db8::1]:443 from fe80::1%eth0, net 2001:db8::/32.";
+        let matches: Vec<_> = extract::extract(text).collect();
+        assert_eq!(matches.iter().map(|m| m.as_str(text)).collect::<Vec<_>>(),
+                   ["2001:db8::1", "fe80::1%eth0", "2001:db8::/32"]);
+        assert_eq!(matches[1].zone, Some(String::from("eth0")));
+        assert_eq!(matches[1].address.to_string(), "fe80::1/128");
+        assert_eq!(matches[2].address.to_string(), "2001:db8::/32");
+        assert_eq!(found("addr:fe80::2: up"), ["fe80::2/128"]);
+        assert_eq!(found("loopback ::1 and ::"), ["::1/128", "::/128"]);
+        assert_eq!(spans("fe80::1%eth0/64", Options::default()), ["fe80::1%eth0/64"]);
+    }
+
+    #[test]
+    pub fn test_options() {
+        let text = "map ::ffff:10.0.0.1 via fe80::1%en0 to 10.0.0.0/24";
+        assert_eq!(spans(text, Options::default()), ["::ffff:10.0.0.1", "fe80::1%en0", "10.0.0.0/24"]);
+        let none = Options { prefixes: false, mapped: false, zone_ids: false };
+        assert_eq!(spans(text, none), ["fe80::1", "10.0.0.0"]);
+        let matches: Vec<_> = extract::extract_with(text, none).collect();
+        assert_eq!(matches[0].zone, None);
+        assert_eq!(matches[1].address.to_string(), "10.0.0.0/32");
+        assert!(extract::extract(text).next().unwrap().address.is_mapped());
+    }
+}
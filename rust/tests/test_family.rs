@@ -0,0 +1,188 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::family::{self, AddressFamily, EUI48, EUI64};
+    use ipaddress::IPAddress;
+    use ipaddress::covering;
+    use ipaddress::wildcard::Wildcard;
+    use ipaddress::network::Network;
+    use ipaddress::subnet_info::{self, SubnetInfo};
+    use num::bigint::BigUint;
+    use num::Num;
+    use std::convert::TryFrom;
+    use std::panic;
+
+    fn eui48(s: &str) -> IPAddress {
+        family::parse(&EUI48, s).unwrap()
+    }
+
+    // 4 bit identifiers written as a single hex digit
+    struct Nibble;
+
+    impl AddressFamily for Nibble {
+        fn name(&self) -> &'static str {
+            "nibble"
+        }
+        fn bits(&self) -> usize {
+            4
+        }
+        fn part_bits(&self) -> usize {
+            4
+        }
+        fn format(&self, addr: &BigUint) -> String {
+            addr.to_str_radix(16)
+        }
+        fn parse(&self, s: &str) -> Result<BigUint, String> {
+            BigUint::from_str_radix(s, 16).map_err(|_| format!("Invalid nibble {}", s))
+        }
+    }
+
+    static NIBBLE: Nibble = Nibble;
+
+    // same name as Nibble, but twice as wide
+    struct WideNibble;
+
+    impl AddressFamily for WideNibble {
+        fn name(&self) -> &'static str {
+            "nibble"
+        }
+        fn bits(&self) -> usize {
+            8
+        }
+        fn format(&self, addr: &BigUint) -> String {
+            addr.to_str_radix(16)
+        }
+        fn parse(&self, s: &str) -> Result<BigUint, String> {
+            BigUint::from_str_radix(s, 16).map_err(|_| format!("Invalid nibble {}", s))
+        }
+    }
+
+    static WIDE_NIBBLE: WideNibble = WideNibble;
+
+    #[test]
+    pub fn test_parse_and_format() {
+        let mac = eui48("00-1B-21-3A-4F-5C");
+        assert_eq!(mac.to_string(), "00:1b:21:3a:4f:5c/48");
+        assert_eq!(mac.to_s(), "00:1b:21:3a:4f:5c");
+        assert_eq!(eui48("001b.213a.4f5c"), mac);
+        assert_eq!(eui48("001b213a4f5c"), mac);
+        assert_eq!(mac.parts(), [0x00, 0x1b, 0x21, 0x3a, 0x4f, 0x5c]);
+        assert!(!mac.is_ipv4() && !mac.is_ipv6());
+        assert!(!mac.is_private() && !mac.is_loopback());
+        assert_eq!(family::parse(&EUI64, "02:1b:21:ff:fe:3a:4f:5c/64").unwrap().to_s(), "02:1b:21:ff:fe:3a:4f:5c");
+
+        assert_eq!(family::parse(&EUI48, "00:1b:21:3a:4f").unwrap_err(), "Invalid EUI-48 00:1b:21:3a:4f");
+        assert!(family::parse(&EUI48, "00:1b:21:3a:4f:5").is_err());
+        assert!(family::parse(&EUI48, "00:1b:21:3a:4f:zz").is_err());
+        assert_eq!(family::parse(&EUI48, "00:1b:21:3a:4f:5c/49").unwrap_err(), "Prefix must be in range 0..48, got: 49");
+        assert!(family::parse(&EUI48, "00:1b:21:3a:4f:5c/x").is_err());
+        assert!(family::from_int(&EUI48, BigUint::from(1u8) << 48, 48).is_err());
+    }
+
+    #[test]
+    pub fn test_prefix() {
+        let prefix = family::prefix(&EUI48, 24).unwrap();
+        assert_eq!(prefix.to_ip_str(), "ff:ff:ff:00:00:00");
+        assert_eq!(prefix.host_prefix(), 24);
+        assert_eq!(prefix.add(4).unwrap().num, 28);
+        assert!(prefix.add(25).is_err());
+        assert!(family::prefix(&EUI48, 24).unwrap() != family::prefix(&EUI64, 24).unwrap());
+    }
+
+    #[test]
+    pub fn test_prefix_math() {
+        let oui = eui48("00:1b:21:3a:4f:5c/24");
+        assert_eq!(oui.network().to_string(), "00:1b:21:00:00:00/24");
+        assert_eq!(oui.broadcast().to_s(), "00:1b:21:ff:ff:ff");
+        assert_eq!(oui.size(), BigUint::from(1u32 << 24));
+        assert!(oui.includes(&eui48("00:1b:21:3a:4f:5c")));
+        assert!(!oui.includes(&eui48("00:1b:22:00:00:01")));
+        assert!(!oui.includes(&family::parse(&EUI64, "00:1b:21:00:00:00:00:01").unwrap()));
+        assert!(!oui.includes(&IPAddress::parse("0.27.33.1").unwrap()));
+
+        let subnets = oui.network().subnet(26).unwrap();
+        assert_eq!(subnets.len(), 4);
+        assert_eq!(subnets[1].to_string(), "00:1b:21:40:00:00/26");
+        assert_eq!(subnets[3].supernet(24).unwrap(), oui.network());
+        assert_eq!(oui.network().split(2).unwrap()[1].to_string(), "00:1b:21:80:00:00/25");
+    }
+
+    #[test]
+    pub fn test_set_operations() {
+        let list = vec![eui48("00:1b:21:00:00:00/25"), eui48("00:1b:21:80:00:00/25"), eui48("00:1b:22:00:00:00/24")];
+        let agg = IPAddress::summarize(&list);
+        assert_eq!(agg.iter().map(|i| i.to_string()).collect::<Vec<_>>(),
+                   ["00:1b:21:00:00:00/24", "00:1b:22:00:00:00/24"]);
+
+        let oui = eui48("00:1b:21:00:00:00/24");
+        let rest = oui.exclude(&[eui48("00:1b:21:00:00:00/25")]);
+        assert_eq!(rest, [eui48("00:1b:21:80:00:00/25")]);
+        assert_eq!(oui.intersection(&eui48("00:1b:21:10:00:00/28")), Some(eui48("00:1b:21:10:00:00/28")));
+
+        let range = oui.range_to_networks(&eui48("00:1b:21:00:00:01").host_address, &eui48("00:1b:21:00:00:03").host_address);
+        assert_eq!(range.iter().map(|i| i.to_string()).collect::<Vec<_>>(),
+                   ["00:1b:21:00:00:01/48", "00:1b:21:00:00:02/47"]);
+    }
+
+    #[test]
+    pub fn test_custom_family() {
+        let net = family::parse(&NIBBLE, "a/3").unwrap();
+        assert_eq!(net.to_string(), "a/3");
+        assert_eq!(net.network().to_s(), "a");
+        assert_eq!(net.broadcast().to_s(), "b");
+        let mut list = [net.clone(), eui48("00:00:00:00:00:01"), IPAddress::parse("::1").unwrap(), IPAddress::parse("10.0.0.1").unwrap()];
+        list.sort();
+        assert!(list[0].is_ipv4() && list[1].is_ipv6());
+        assert_eq!(list[2], eui48("00:00:00:00:00:01"));
+        assert_eq!(list[3], net);
+    }
+
+    #[test]
+    pub fn test_family_specific_code() {
+        assert_eq!(family::parse(&NIBBLE, "a").unwrap().octets(), [0x0a]);
+        assert_eq!(eui48("00:1b:21:3a:4f:5c").octets(), [0x00, 0x1b, 0x21, 0x3a, 0x4f, 0x5c]);
+
+        let mac = eui48("00:1b:21:3a:4f:5c");
+        assert_eq!(Network::try_from(mac.clone()).unwrap_err(), "00:1b:21:3a:4f:5c/48 is not an IP address");
+        assert!(Network::try_from(IPAddress::parse("::1").unwrap()).unwrap().is_ipv6());
+
+        assert_eq!(subnet_info::special_purpose(&eui48("00:00:00:00:00:01")), None);
+        assert_eq!(subnet_info::classful(&mac), None);
+        assert!(SubnetInfo::new(&mac).to_json().starts_with("{\"version\":\"EUI-48\","));
+
+        let report = covering::report(&[eui48("00:1b:21:00:00:01"), IPAddress::parse("::1").unwrap(),
+                                        eui48("00:1b:21:00:00:06"), family::parse(&NIBBLE, "a").unwrap()]);
+        assert_eq!(report.iter().map(|i| i.network.to_string()).collect::<Vec<_>>(),
+                   ["::1/128", "00:1b:21:00:00:00/45", "a/4"]);
+
+        assert_eq!(IPAddress::from_u128(mac.ip_bits.version, mac.to_u128(), 48).unwrap_err(),
+                   "EUI-48 addresses can not be built from a u128");
+        assert!(Wildcard::from_network(&mac).is_err());
+    }
+
+    #[test]
+    pub fn test_family_identity() {
+        let narrow = family::parse(&NIBBLE, "a/4").unwrap();
+        let wide = family::parse(&WIDE_NIBBLE, "a/8").unwrap();
+        assert!(narrow.ip_bits.version != wide.ip_bits.version);
+        assert!(narrow.ip_bits.version < wide.ip_bits.version);
+        assert!(narrow != family::parse(&WIDE_NIBBLE, "a/4").unwrap());
+        assert!(!wide.includes(&narrow));
+    }
+
+    #[test]
+    pub fn test_no_reverse_zone() {
+        assert!(eui48("00:1b:21:3a:4f:5c/24").dns_rev_domains().is_empty());
+        assert!(family::parse(&EUI64, "02:1b:21:ff:fe:3a:4f:5c").unwrap().dns_rev_domains().is_empty());
+        assert_eq!(IPAddress::parse("172.17.100.50/15").unwrap().dns_rev_domains(),
+                   ["16.172.in-addr.arpa", "17.172.in-addr.arpa"]);
+    }
+
+    #[test]
+    pub fn test_unwind_safe() {
+        let mac = eui48("00:1b:21:3a:4f:5c");
+        assert_eq!(panic::catch_unwind(|| mac.to_s()).unwrap(), "00:1b:21:3a:4f:5c");
+    }
+}
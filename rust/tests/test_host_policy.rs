@@ -0,0 +1,85 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::host_policy::HostPolicy;
+    use num::bigint::BigUint;
+    use num::FromPrimitive;
+    use std::sync::{Arc, Mutex};
+
+    fn count(n: usize) -> BigUint {
+        BigUint::from_usize(n).unwrap()
+    }
+
+    fn hosts(ip: &str, policy: HostPolicy) -> Vec<String> {
+        let arr = Arc::new(Mutex::new(Vec::new()));
+        IPAddress::parse(ip).unwrap().each_host_with(&policy, |i| arr.lock().unwrap().push(i.to_s()));
+        let ret = arr.lock().unwrap().clone();
+        ret
+    }
+
+    #[test]
+    pub fn test_classic() {
+        let ip = IPAddress::parse("192.168.100.50/24").unwrap();
+        assert_eq!(ip.first_with(&HostPolicy::Classic).unwrap().to_s(), "192.168.100.1");
+        assert_eq!(ip.last_with(&HostPolicy::Classic).unwrap().to_s(), "192.168.100.254");
+        assert_eq!(ip.host_count(), count(254));
+        assert_eq!(ip.size(), count(256));
+        let ip = IPAddress::parse("10.0.0.0/31").unwrap();
+        assert_eq!(ip.host_count(), count(0));
+        assert!(ip.first_with(&HostPolicy::Classic).is_none());
+        assert_eq!(ip.first().to_s(), "10.0.0.0");
+        assert_eq!(ip.last().to_s(), "10.0.0.1");
+        assert_eq!(hosts("10.0.0.1/32", HostPolicy::Classic).len(), 0);
+        let ip = IPAddress::parse("10.0.0.5/32").unwrap();
+        assert_eq!(ip.host_count(), count(0));
+        assert!(ip.last_with(&HostPolicy::Classic).is_none());
+        assert_eq!(ip.first().to_s(), "10.0.0.5");
+        assert_eq!(ip.last().to_s(), "10.0.0.5");
+        let ip = IPAddress::parse("255.255.255.255/32").unwrap();
+        assert_eq!(ip.first().to_s(), "255.255.255.255");
+        assert_eq!(ip.last().to_s(), "255.255.255.255");
+        let ip = IPAddress::parse("2001:db8::/126").unwrap();
+        assert_eq!(ip.host_count(), count(4));
+        assert_eq!(ip.first().to_s(), "2001:db8::");
+    }
+
+    #[test]
+    pub fn test_rfc3021() {
+        assert_eq!(hosts("10.0.0.0/31", HostPolicy::Rfc3021), ["10.0.0.0", "10.0.0.1"]);
+        assert_eq!(hosts("10.0.0.7/32", HostPolicy::Rfc3021), ["10.0.0.7"]);
+        assert_eq!(hosts("10.0.0.0/30", HostPolicy::Rfc3021), ["10.0.0.1", "10.0.0.2"]);
+        assert_eq!(hosts("2001:db8::/127", HostPolicy::Rfc3021), ["2001:db8::", "2001:db8::1"]);
+        let ip = IPAddress::parse("0.0.0.0/32").unwrap();
+        assert_eq!(ip.last_with(&HostPolicy::Rfc3021).unwrap().to_s(), "0.0.0.0");
+    }
+
+    #[test]
+    pub fn test_subnet_router_anycast() {
+        assert_eq!(hosts("2001:db8::/126", HostPolicy::SubnetRouterAnycast),
+                   ["2001:db8::1", "2001:db8::2", "2001:db8::3"]);
+        assert_eq!(hosts("2001:db8::/127", HostPolicy::SubnetRouterAnycast).len(), 2);
+        let ip = IPAddress::parse("2001:db8::/64").unwrap();
+        assert_eq!(ip.host_count_with(&HostPolicy::SubnetRouterAnycast),
+                   ip.size() - count(1));
+        assert_eq!(hosts("10.0.0.0/30", HostPolicy::SubnetRouterAnycast), ["10.0.0.1", "10.0.0.2"]);
+    }
+
+    #[test]
+    pub fn test_reserved() {
+        let cloud = HostPolicy::Reserved { head: 4, tail: 1 };
+        let ip = IPAddress::parse("10.0.0.0/24").unwrap();
+        assert_eq!(ip.host_count_with(&cloud), count(251));
+        assert_eq!(ip.first_with(&cloud).unwrap().to_s(), "10.0.0.4");
+        assert_eq!(ip.last_with(&cloud).unwrap().to_s(), "10.0.0.254");
+        assert_eq!(hosts("10.0.0.0/29", cloud), ["10.0.0.4", "10.0.0.5", "10.0.0.6"]);
+        assert_eq!(hosts("10.0.0.0/30", cloud).len(), 0);
+        assert!(cloud.is_usable(&ip, &IPAddress::parse("10.0.0.4").unwrap()));
+        assert!(!cloud.is_usable(&ip, &IPAddress::parse("10.0.0.3").unwrap()));
+        assert!(!cloud.is_usable(&ip, &IPAddress::parse("10.0.0.255").unwrap()));
+        assert!(!cloud.is_usable(&ip, &IPAddress::parse("10.0.1.10").unwrap()));
+        assert_eq!(HostPolicy::default(), HostPolicy::Classic);
+    }
+}
@@ -3,7 +3,10 @@ extern crate num;
 
 #[cfg(test)]
 mod tests {
-    use ipaddress::IPAddress;
+    use ipaddress::{IPAddress, Parts};
+    use ipaddress::ip_bits::IpVersion;
+    use num::bigint::BigUint;
+    use num::Zero;
 
     // use std::str::FromStr;
 
@@ -208,4 +211,221 @@ mod tests {
         assert_eq!("10.0.0.1/24", a1.to_string());
         assert_eq!("10.0.1.1/24", a2.to_string());
     }
+    #[test]
+    pub fn test_range_to_networks() {
+        let ip = IPAddress::parse("10.0.0.0/8").unwrap();
+        let first = IPAddress::parse("10.0.0.1").unwrap().host_address;
+        let last = IPAddress::parse("10.0.0.6").unwrap().host_address;
+        assert_eq!(IPAddress::to_string_vec(&ip.range_to_networks(&first, &last)),
+                   ["10.0.0.1/32", "10.0.0.2/31", "10.0.0.4/31", "10.0.0.6/32"]);
+        let all = IPAddress::parse("255.255.255.255").unwrap().host_address;
+        assert_eq!(IPAddress::to_string_vec(&ip.range_to_networks(&BigUint::zero(), &all)),
+                   ["0.0.0.0/0"]);
+        assert_eq!(ip.range_to_networks(&last, &first).len(), 0);
+    }
+    #[test]
+    pub fn test_exclude() {
+        let ip = IPAddress::parse("10.0.0.0/24").unwrap();
+        assert_eq!(IPAddress::to_string_vec(&ip.exclude(&[IPAddress::parse("10.0.0.64/26").unwrap()])),
+                   ["10.0.0.0/26", "10.0.0.128/25"]);
+        assert_eq!(IPAddress::to_string_vec(&ip.exclude(&[IPAddress::parse("10.0.0.0/25").unwrap(),
+                                                          IPAddress::parse("10.0.0.0/26").unwrap(),
+                                                          IPAddress::parse("2001:db8::/32").unwrap()])),
+                   ["10.0.0.128/25"]);
+        assert_eq!(ip.exclude(&[IPAddress::parse("10.0.0.0/8").unwrap()]).len(), 0);
+        assert_eq!(IPAddress::to_string_vec(&ip.exclude(&[])), ["10.0.0.0/24"]);
+    }
+    #[test]
+    pub fn test_intersection() {
+        let ip = IPAddress::parse("192.168.10.1/24").unwrap();
+        assert_eq!(ip.intersection(&IPAddress::parse("192.168.10.65/26").unwrap()).unwrap().to_string(),
+                   "192.168.10.64/26");
+        assert_eq!(ip.intersection(&IPAddress::parse("192.168.0.0/16").unwrap()).unwrap().to_string(),
+                   "192.168.10.0/24");
+        assert!(ip.intersection(&IPAddress::parse("192.168.11.0/24").unwrap()).is_none());
+        assert!(ip.intersection(&IPAddress::parse("::/0").unwrap()).is_none());
+    }
+    #[test]
+    pub fn test_arithmetic() {
+        let ip = IPAddress::parse("10.0.0.1/24").unwrap();
+        assert_eq!((&ip + 5).to_string(), "10.0.0.6/24");
+        assert_eq!((&ip - 1).to_string(), "10.0.0.0/24");
+        assert_eq!(ip.next().unwrap().to_string(), "10.0.0.2/24");
+        assert_eq!(ip.prev().unwrap().to_string(), "10.0.0.0/24");
+        assert_eq!(ip.checked_add(255).unwrap().to_string(), "10.0.1.0/24");
+
+        let top = IPAddress::parse("255.255.255.254/8").unwrap();
+        assert!(top.checked_add(2).is_none());
+        assert!(top.next().unwrap().next().is_none());
+        assert_eq!(top.wrapping_add(3).to_string(), "0.0.0.1/8");
+        assert_eq!(top.saturating_add(u128::MAX).to_string(), "255.255.255.255/8");
+
+        let bottom = IPAddress::parse("0.0.0.1/8").unwrap();
+        assert!(bottom.checked_sub(2).is_none());
+        assert_eq!(bottom.wrapping_sub(2).to_string(), "255.255.255.255/8");
+        assert_eq!(bottom.saturating_sub(7).to_string(), "0.0.0.0/8");
+
+        let ip6 = IPAddress::parse("ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/64").unwrap();
+        assert!(ip6.checked_add(2).is_none());
+        assert_eq!(ip6.wrapping_add(u128::MAX).to_string(),
+                   "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffd/64");
+        assert_eq!(IPAddress::parse("::/64").unwrap().saturating_add(u128::MAX).to_string(),
+                   "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/64");
+        assert!(IPAddress::parse("::").unwrap().prev().is_none());
+    }
+    #[test]
+    pub fn test_arithmetic_mapped() {
+        let ip = IPAddress::parse("::ffff:10.0.0.1").unwrap();
+        let next = ip.checked_add(1).unwrap();
+        assert_eq!(next, IPAddress::parse("::ffff:10.0.0.2").unwrap());
+        assert_eq!(next.mapped.unwrap().to_s(), "10.0.0.2");
+        assert_eq!(ip.prev().unwrap(), IPAddress::parse("::ffff:10.0.0.0").unwrap());
+        assert_eq!(ip.wrapping_add(255), IPAddress::parse("::ffff:10.0.1.0").unwrap());
+        let net = IPAddress::parse("::ffff:10.0.0.1/24").unwrap();
+        assert_eq!(net.checked_add(2).unwrap(), IPAddress::parse("::ffff:10.0.0.3/24").unwrap());
+        assert_eq!(net.nth_host(4).unwrap(), IPAddress::parse("::ffff:10.0.0.4/24").unwrap());
+        let top = IPAddress::parse("::ffff:255.255.255.255").unwrap();
+        assert!(top.checked_add(1).unwrap().mapped.is_none());
+    }
+    #[test]
+    #[should_panic(expected = "address overflow")]
+    pub fn test_add_overflow() {
+        let _ = &IPAddress::parse("255.255.255.255").unwrap() + 1;
+    }
+    #[test]
+    pub fn test_arithmetic_in_network() {
+        let ip = IPAddress::parse("10.0.0.250/24").unwrap();
+        assert_eq!(ip.checked_add_in_network(5).unwrap().to_string(), "10.0.0.255/24");
+        assert!(ip.checked_add_in_network(6).is_none());
+        assert_eq!(ip.checked_sub_in_network(250).unwrap().to_string(), "10.0.0.0/24");
+        assert!(ip.checked_sub_in_network(251).is_none());
+    }
+    #[test]
+    pub fn test_nth_host_and_distance() {
+        let ip = IPAddress::parse("192.168.1.77/24").unwrap();
+        assert_eq!(ip.nth_host(0).unwrap().to_string(), "192.168.1.1/24");
+        assert_eq!(ip.nth_host(253).unwrap().to_string(), "192.168.1.254/24");
+        assert!(ip.nth_host(254).is_none());
+        assert!(IPAddress::parse("10.0.0.0/32").unwrap().nth_host(0).is_none());
+        assert_eq!(IPAddress::parse("2001:db8::/64").unwrap().nth_host(0).unwrap().to_string(),
+                   "2001:db8::/64");
+
+        let ip1 = IPAddress::parse("10.0.0.10").unwrap();
+        let ip2 = IPAddress::parse("10.0.0.1").unwrap();
+        assert_eq!(ip1.distance(&ip2).unwrap().to_string(), "-9");
+        assert_eq!(ip2.distance(&ip1).unwrap().to_string(), "9");
+        assert_eq!(ip1.distance(&ip1).unwrap().to_string(), "0");
+        assert!(ip1.distance(&IPAddress::parse("::a").unwrap()).is_err());
+    }
+    #[test]
+    pub fn test_bitwise_operators() {
+        let ip = IPAddress::parse("10.1.2.3/8").unwrap();
+        let mask = IPAddress::parse("255.255.0.0").unwrap();
+        assert_eq!((&ip & &mask).to_string(), "10.1.0.0/8");
+        assert_eq!((&ip | &IPAddress::parse("0.0.0.252").unwrap()).to_string(), "10.1.2.255/8");
+        assert_eq!((&ip ^ &IPAddress::parse("10.1.2.2").unwrap()).to_string(), "0.0.0.1/8");
+        assert_eq!((!&mask).to_string(), "0.0.255.255/32");
+        assert_eq!((!&IPAddress::parse("::/0").unwrap()).to_s(), "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff");
+    }
+    #[test]
+    pub fn test_bitwise_mapped() {
+        let ip = IPAddress::parse("::ffff:10.1.2.3").unwrap();
+        let mask = IPAddress::parse("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ff00").unwrap();
+        assert_eq!(&ip & &mask, IPAddress::parse("::ffff:10.1.2.0").unwrap());
+        assert_eq!(&ip | &IPAddress::parse("::ff").unwrap(), IPAddress::parse("::ffff:10.1.2.255").unwrap());
+        assert_eq!(&ip ^ &IPAddress::parse("::3").unwrap(), IPAddress::parse("::ffff:10.1.2.0").unwrap());
+        assert_eq!(ip.set_bit(127, false), IPAddress::parse("::ffff:10.1.2.2").unwrap());
+        assert_eq!((&ip & &mask).mapped.unwrap().to_s(), "10.1.2.0");
+        assert!((!&ip).mapped.is_none());
+        assert!(ip.set_bit(80, false).mapped.is_none());
+    }
+    #[test]
+    #[should_panic(expected = "different families")]
+    pub fn test_bitwise_mixed_families() {
+        let _ = &IPAddress::parse("10.0.0.1").unwrap() & &IPAddress::parse("::1").unwrap();
+    }
+    #[test]
+    pub fn test_bit_access() {
+        let ip = IPAddress::parse("128.0.0.1").unwrap();
+        assert!(ip.bit(0));
+        assert!(!ip.bit(1));
+        assert!(ip.bit(31));
+        assert_eq!(IPAddress::parse("10.0.0.0/8").unwrap().set_bit(31, true).to_string(), "10.0.0.1/8");
+        assert_eq!(ip.set_bit(0, false).to_s(), "0.0.0.1");
+        assert_eq!(ip.set_bit(0, true), ip);
+        assert!(IPAddress::parse("::1").unwrap().bit(127));
+
+        assert_eq!(IPAddress::parse("0.0.255.255").unwrap().leading_zeros(), 16);
+        assert_eq!(IPAddress::parse("0.0.0.0").unwrap().leading_zeros(), 32);
+        assert_eq!(IPAddress::parse("::1").unwrap().leading_zeros(), 127);
+        assert_eq!(ip.leading_zeros(), 0);
+    }
+    #[test]
+    pub fn test_common_prefix_len() {
+        let a = IPAddress::parse("10.0.0.1").unwrap();
+        assert_eq!(IPAddress::common_prefix_len(&a, &IPAddress::parse("10.0.0.6").unwrap()), 29);
+        assert_eq!(IPAddress::common_prefix_len(&a, &a), 32);
+        assert_eq!(IPAddress::common_prefix_len(&a, &IPAddress::parse("138.0.0.1").unwrap()), 0);
+        assert_eq!(IPAddress::common_prefix_len(&a, &IPAddress::parse("::1").unwrap()), 0);
+        assert_eq!(IPAddress::common_prefix_len(&IPAddress::parse("2001:db8::").unwrap(),
+                                                &IPAddress::parse("2001:db9::").unwrap()), 31);
+    }
+    #[test]
+    pub fn test_from_octets() {
+        assert_eq!(IPAddress::from_octets(&[10, 0, 0, 1], 8).unwrap().to_string(), "10.0.0.1/8");
+        let ip6 = IPAddress::parse("2001:db8::1/64").unwrap();
+        assert_eq!(IPAddress::from_octets(&ip6.octets(), 64).unwrap(), ip6);
+        let packet = [0x45u8, 0, 0, 0x54, 192, 168, 1, 10, 8, 8, 8, 8];
+        assert_eq!(IPAddress::from_octets(&packet[4..8], 32).unwrap().to_s(), "192.168.1.10");
+        assert!(IPAddress::from_octets(&packet[4..9], 32).is_err());
+        assert!(IPAddress::from_octets(&[], 0).is_err());
+        assert!(IPAddress::from_octets(&[1, 2, 3, 4], 33).is_err());
+    }
+    #[test]
+    pub fn test_from_u128() {
+        assert_eq!(IPAddress::from_u128(IpVersion::V4, 0x0a000001, 8).unwrap().to_string(), "10.0.0.1/8");
+        assert_eq!(IPAddress::from_u128(IpVersion::V6, 0x20010db8 << 96, 32).unwrap().to_string(), "2001:db8::/32");
+        assert!(IPAddress::from_u128(IpVersion::V4, 1 << 32, 32).is_err());
+        assert!(IPAddress::from_u128(IpVersion::V4, 1, 33).is_err());
+        let ip = IPAddress::parse("2001:db8::1/64").unwrap();
+        assert_eq!(IPAddress::from_u128(ip.ip_bits.version, ip.to_u128(), 64).unwrap(), ip);
+    }
+    #[test]
+    pub fn test_parts_access() {
+        let ip = IPAddress::parse("192.168.1.77/24").unwrap();
+        assert_eq!(ip.part(0), Some(192));
+        assert_eq!(ip.part(4), None);
+        assert_eq!(ip.octet(3), Some(77));
+        assert_eq!(ip.octet(2), Some(1));
+        let parts: Parts = ip.to_parts();
+        assert_eq!(parts[2], 1);
+        assert_eq!(ip.to_parts().as_slice(), [192, 168, 1, 77]);
+        assert_eq!(ip.octet(4), None);
+        assert_eq!(ip.with_octet(3, 254).unwrap().to_string(), "192.168.1.254/24");
+        assert_eq!(ip.with_part(0, 10).unwrap().to_string(), "10.168.1.77/24");
+        assert!(ip.with_part(0, 256).is_err());
+        assert!(ip.with_octet(4, 1).is_err());
+        assert_eq!(ip.hextet(0), None);
+        assert!(ip.with_hextet(0, 1).is_err());
+
+        let ip6 = IPAddress::parse("2001:db8::8:800:200c:417a/64").unwrap();
+        assert_eq!(ip6.hextet(1), Some(0xdb8));
+        assert_eq!(ip6.hextet(7), Some(0x417a));
+        assert_eq!(ip6.to_parts()[7], 0x417a);
+        assert_eq!(ip6.to_parts().len(), 8);
+        assert_eq!(ip6.octet(0), None);
+        assert_eq!(ip6.with_hextet(7, 1).unwrap().to_string(), "2001:db8::8:800:200c:1/64");
+        assert_eq!(ip6.with_hextet(0, 0xffff).unwrap().to_string(), "ffff:db8::8:800:200c:417a/64");
+        assert!(ip6.with_octet(0, 1).is_err());
+        assert!(ip6.with_hextet(8, 1).is_err());
+
+        let mapped = IPAddress::parse("::ffff:10.0.0.1").unwrap();
+        assert_eq!(mapped.with_hextet(7, 0xfe).unwrap(), IPAddress::parse("::ffff:10.0.0.254").unwrap());
+        assert!(mapped.with_hextet(5, 0).unwrap().mapped.is_none());
+    }
+    #[test]
+    #[should_panic(expected = "index 4 out of range for 4 parts")]
+    pub fn test_index_out_of_range() {
+        let _ = IPAddress::parse("10.0.0.1").unwrap().to_parts()[4];
+    }
 }
@@ -0,0 +1,126 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::ip_bits::IpVersion;
+    use ipaddress::ipam::{Allocator, Policy};
+    use num::bigint::BigUint;
+    use num::FromPrimitive;
+
+    fn ip(s: &str) -> IPAddress {
+        IPAddress::parse(s).unwrap()
+    }
+
+    fn setup() -> Allocator {
+        let mut ipam = Allocator::new();
+        ipam.add_pool(&ip("10.0.0.0/16")).unwrap();
+        ipam.add_pool(&ip("2001:db8::/48")).unwrap();
+        ipam
+    }
+
+    #[test]
+    pub fn test_add_pool() {
+        let mut ipam = setup();
+        assert_eq!(ipam.pools().len(), 2);
+        assert!(ipam.add_pool(&ip("10.0.128.0/17")).is_err());
+        assert!(ipam.add_pool(&ip("10.0.0.0/8")).is_err());
+        assert!(ipam.add_pool(&ip("10.1.2.3/16")).is_ok());
+        assert_eq!(ipam.pools()[2].network.to_string(), "10.1.0.0/16");
+        assert!(ipam.remove_pool(&ip("10.1.0.0/16")).is_ok());
+        assert!(ipam.remove_pool(&ip("10.1.0.0/16")).is_err());
+    }
+
+    #[test]
+    pub fn test_first_fit() {
+        let mut ipam = setup();
+        assert_eq!(ipam.allocate(IpVersion::V4, 24, Policy::FirstFit).unwrap().to_string(), "10.0.0.0/24");
+        assert_eq!(ipam.allocate(IpVersion::V4, 26, Policy::FirstFit).unwrap().to_string(), "10.0.1.0/26");
+        assert_eq!(ipam.allocate(IpVersion::V4, 25, Policy::FirstFit).unwrap().to_string(), "10.0.1.128/25");
+        assert_eq!(ipam.allocate(IpVersion::V4, 26, Policy::FirstFit).unwrap().to_string(), "10.0.1.64/26");
+        assert_eq!(ipam.allocate(IpVersion::V6, 64, Policy::FirstFit).unwrap().to_string(), "2001:db8::/64");
+        assert!(ipam.allocate(IpVersion::V4, 8, Policy::FirstFit).is_err());
+    }
+
+    #[test]
+    pub fn test_mixed_families() {
+        let mut ipam = Allocator::new();
+        ipam.add_pool(&ip("2001:db8::/32")).unwrap();
+        ipam.add_pool(&ip("10.0.0.0/16")).unwrap();
+        assert_eq!(ipam.allocate(IpVersion::V4, 24, Policy::FirstFit).unwrap().to_string(), "10.0.0.0/24");
+        assert_eq!(ipam.allocate(IpVersion::V6, 48, Policy::FirstFit).unwrap().to_string(), "2001:db8::/48");
+        assert!(ipam.allocate(IpVersion::V4, 12, Policy::FirstFit).is_err());
+
+        let mut ipam = Allocator::new();
+        ipam.add_pool(&ip("10.0.0.0/16")).unwrap();
+        ipam.add_pool(&ip("2001:db8::/32")).unwrap();
+        assert_eq!(ipam.allocate(IpVersion::V6, 40, Policy::FirstFit).unwrap().to_string(), "2001:db8::/40");
+        assert_eq!(ipam.allocate(IpVersion::V4, 24, Policy::BestFit).unwrap().to_string(), "10.0.0.0/24");
+        assert!(ipam.allocate(IpVersion::V6, 24, Policy::FirstFit).is_err());
+    }
+
+    #[test]
+    pub fn test_best_fit() {
+        let mut ipam = setup();
+        ipam.allocate_network(&ip("10.0.0.0/26")).unwrap();
+        ipam.allocate_network(&ip("10.0.0.128/26")).unwrap();
+        // 10.0.0.64/26 is the smallest hole
+        assert_eq!(ipam.allocate(IpVersion::V4, 27, Policy::BestFit).unwrap().to_string(), "10.0.0.64/27");
+        assert_eq!(ipam.allocate(IpVersion::V4, 27, Policy::FirstFit).unwrap().to_string(), "10.0.0.96/27");
+        assert_eq!(ipam.allocate(IpVersion::V4, 27, Policy::BestFit).unwrap().to_string(), "10.0.0.192/27");
+    }
+
+    #[test]
+    pub fn test_reserve_and_free() {
+        let mut ipam = setup();
+        ipam.reserve(&ip("10.0.0.0/24")).unwrap();
+        assert!(ipam.reserve(&ip("10.0.0.128/25")).is_err());
+        assert!(ipam.reserve(&ip("10.0.1.1/24")).is_err());
+        assert!(ipam.reserve(&ip("192.168.0.0/24")).is_err());
+        let net = ipam.allocate(IpVersion::V4, 24, Policy::FirstFit).unwrap();
+        assert_eq!(net.to_string(), "10.0.1.0/24");
+        assert!(ipam.allocate_network(&ip("10.0.1.0/25")).is_err());
+        ipam.free(&net).unwrap();
+        assert!(ipam.free(&net).is_err());
+        ipam.unreserve(&ip("10.0.0.0/24")).unwrap();
+        assert_eq!(ipam.allocate(IpVersion::V4, 24, Policy::FirstFit).unwrap().to_string(), "10.0.0.0/24");
+    }
+
+    #[test]
+    pub fn test_usage() {
+        let mut ipam = setup();
+        let pool = ip("10.0.0.0/16");
+        ipam.reserve(&ip("10.0.0.0/18")).unwrap();
+        ipam.allocate(IpVersion::V4, 17, Policy::FirstFit).unwrap();
+        let usage = ipam.usage(&pool).unwrap();
+        assert_eq!(usage.total, BigUint::from_u32(65536).unwrap());
+        assert_eq!(usage.reserved, BigUint::from_u32(16384).unwrap());
+        assert_eq!(usage.allocated, BigUint::from_u32(32768).unwrap());
+        assert_eq!(usage.free, BigUint::from_u32(16384).unwrap());
+        assert_eq!(usage.utilization(), 75.0);
+        assert_eq!(IPAddress::to_string_vec(&ipam.free_blocks(&pool).unwrap()),
+                   ["10.0.64.0/18"]);
+        assert!(ipam.usage(&ip("10.1.0.0/16")).is_err());
+    }
+
+    #[test]
+    pub fn test_dump_load() {
+        let mut ipam = setup();
+        ipam.reserve(&ip("10.0.0.0/24")).unwrap();
+        ipam.allocate(IpVersion::V4, 26, Policy::FirstFit).unwrap();
+        ipam.allocate(IpVersion::V6, 56, Policy::FirstFit).unwrap();
+        let state = ipam.dump();
+        assert_eq!(state,
+                   "pool 10.0.0.0/16\n\
+                    reserved 10.0.0.0/24\n\
+                    allocated 10.0.1.0/26\n\
+                    pool 2001:db8::/48\n\
+                    allocated 2001:db8::/56\n");
+        let loaded = Allocator::load(&format!("# saved\n\n{}", state)).unwrap();
+        assert_eq!(loaded.dump(), state);
+        assert!(Allocator::load("pool 10.0.0.0/16\nallocated 10.1.0.0/24\n").is_err());
+        assert!(Allocator::load("pool\n").is_err());
+        assert!(Allocator::load("subnet 10.0.0.0/8\n").is_err());
+    }
+}
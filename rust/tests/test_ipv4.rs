@@ -129,7 +129,7 @@ mod tests {
             let ip = IPAddress::parse(i.to_string()).unwrap();
             assert!(ip.is_ipv4() && !ip.is_ipv6());
         }
-        assert_eq!(32, setup.ip.prefix.ip_bits.bits);
+        assert_eq!(32, setup.ip.prefix.ip_bits().bits);
         assert!(IPAddress::parse("1.f.13.1/-3").is_err());
         assert!(IPAddress::parse("10.0.0.0/8").is_ok());
     }
@@ -354,6 +354,14 @@ mod tests {
         assert_eq!(false, ipv4::is_class_c(&setup().class_b));
     }
     #[test]
+    pub fn test_method_d_e() {
+        assert!(ipv4::is_class_d(&IPAddress::parse("224.0.0.1").unwrap()));
+        assert!(!ipv4::is_class_d(&setup().class_c));
+        assert!(!ipv4::is_class_d(&IPAddress::parse("240.0.0.1").unwrap()));
+        assert!(ipv4::is_class_e(&IPAddress::parse("255.255.255.255").unwrap()));
+        assert!(!ipv4::is_class_e(&IPAddress::parse("239.255.255.255").unwrap()));
+    }
+    #[test]
     pub fn test_method_to_ipv6() {
         assert_eq!("::ac10:a01", setup().ip.to_ipv6().to_s());
     }
@@ -660,4 +668,18 @@ mod tests {
         }
         assert!(ipv4::parse_classful("192.168.256.257").is_err());
     }
+    #[test]
+    pub fn test_classmethod_from_octets() {
+        assert_eq!(ipv4::from_octets([172, 16, 10, 1], 24).unwrap().to_string(), "172.16.10.1/24");
+        assert!(ipv4::from_octets([172, 16, 10, 1], 33).is_err());
+    }
+    #[test]
+    pub fn test_method_octets_and_integers() {
+        let ip = IPAddress::parse("172.16.10.1/24").unwrap();
+        assert_eq!(ip.octets(), [172, 16, 10, 1]);
+        assert_eq!(IPAddress::parse("0.0.0.0").unwrap().octets(), [0, 0, 0, 0]);
+        assert_eq!(ip.to_u32(), Some(2886732289));
+        assert_eq!(ip.to_u128(), 2886732289);
+        assert_eq!(ipv4::from_u32(ip.to_u32().unwrap(), 24).unwrap(), ip);
+    }
 }
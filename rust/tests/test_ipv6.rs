@@ -417,4 +417,22 @@ mod tests {
         assert_eq!(setup().ip.to_string(),
                    ipv6::from_str(setup().hex, 16, 64).unwrap().to_string());
     }
+    #[test]
+    fn test_classmethod_from_u128() {
+        assert_eq!(ipv6::from_u128(0x20010db8 << 96, 32).unwrap().to_string(), "2001:db8::/32");
+        assert_eq!(ipv6::from_u128(u128::MAX, 128).unwrap().to_s(),
+                   "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff");
+        assert!(ipv6::from_u128(1, 129).is_err());
+        let ip = IPAddress::parse("2001:db8::8:800:200c:417a/64").unwrap();
+        assert_eq!(ipv6::from_u128(ip.to_u128(), 64).unwrap(), ip);
+        assert_eq!(ip.to_u32(), None);
+    }
+    #[test]
+    fn test_method_octets() {
+        let ip = IPAddress::parse("2001:db8::8:800:200c:417a/64").unwrap();
+        let octets = [0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0x08, 0x08, 0, 0x20, 0x0c, 0x41, 0x7a];
+        assert_eq!(ip.octets(), octets);
+        assert_eq!(ipv6::from_octets(octets, 64).unwrap(), ip);
+        assert_eq!(IPAddress::parse("::1").unwrap().octets(), [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    }
 }
@@ -0,0 +1,108 @@
+#[macro_use]
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::ip_bits::IpVersion;
+    use ipaddress::literal::IpLiteral;
+
+    const PRIVATE: IpLiteral = IpLiteral::parse("172.16.0.0/12");
+    const DOC: [IpLiteral; 2] = [IpLiteral::v4(0xc0000200, 24), IpLiteral::v6(0x20010db8 << 96, 32)];
+
+    #[test]
+    pub fn test_const() {
+        assert_eq!(PRIVATE, IpLiteral { version: IpVersion::V4, addr: 0xac100000, prefix: 12 });
+        let ip: IPAddress = PRIVATE.into();
+        assert_eq!(ip.to_string(), "172.16.0.0/12");
+        assert_eq!(DOC[0].to_ipaddress().to_string(), "192.0.2.0/24");
+        assert_eq!(DOC[1].to_ipaddress().to_string(), "2001:db8::/32");
+    }
+
+    #[test]
+    pub fn test_macro() {
+        for s in ["10.0.0.0/8", "192.168.1.77", "0.0.0.0/0", "255.255.255.255/32"].iter() {
+            assert_eq!(IPAddress::parse(*s).unwrap(), IpLiteral::parse(s).to_ipaddress());
+        }
+        for s in ["::", "::1", "1::", "2001:db8::8:800:200c:417a/64", "fe80::1:2/10",
+                  "2001:0DB8:0:0:8:800:200C:417A", "1:2:3:4:5:6:7::", "::2:3:4:5:6:7:8"].iter() {
+            assert_eq!(IPAddress::parse(*s).unwrap(), IpLiteral::parse(s).to_ipaddress());
+        }
+        assert_eq!(ip!("10.0.0.0/8").to_string(), "10.0.0.0/8");
+        assert_eq!(ip!("2001:db8::1/64").to_string(), "2001:db8::1/64");
+        assert!(ip!("::").to_string() == "::/128");
+    }
+
+    #[test]
+    pub fn test_invalid() {
+        for s in ["10.0.0.256", "10.0.0", "10.0.0.0.0", "10..0.0", "10.0.0.0/33", "10.0.0.0/", "10.0.0.1000",
+                  "::/129", "1:::2", "1::2::3", "1:2:3:4:5:6:7", "1:2:3:4:5:6:7:8:9", "12345::", "1:",
+                  "g::", "", "10.0.0.0/8/8"].iter() {
+            assert!(std::panic::catch_unwind(|| IpLiteral::parse(s)).is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    pub fn test_includes() {
+        assert!(PRIVATE.includes(&IPAddress::parse("172.31.1.1/24").unwrap()));
+        assert!(!PRIVATE.includes(&IPAddress::parse("172.32.0.0/24").unwrap()));
+        assert!(!PRIVATE.includes(&IPAddress::parse("172.16.0.0/8").unwrap()));
+        assert!(!PRIVATE.includes(&IPAddress::parse("::ac10:1").unwrap()));
+        assert!(IpLiteral::parse("::/0").includes(&IPAddress::parse("2001:db8::1").unwrap()));
+        assert!(IpLiteral::parse("0.0.0.0/0").includes(&IPAddress::parse("1.2.3.4").unwrap()));
+    }
+
+    #[test]
+    pub fn test_try_parse() {
+        assert_eq!("10.0.0.1/8".parse::<IpLiteral>(), Ok(IpLiteral::v4(0x0a000001, 8)));
+        assert_eq!(IpLiteral::try_parse("10.0.0.256"), Err("invalid IPv4 part in IP literal"));
+        assert_eq!(IpLiteral::try_parse("10.0.0.0/33"), Err("IPv4 prefix must be in range 0..32"));
+        assert_eq!(IpLiteral::try_parse("1::2::3"), Err("IPv6 literal allows only one ::"));
+        assert!(IpLiteral::try_parse("g::").is_err());
+    }
+
+    #[test]
+    pub fn test_display() {
+        for s in ["10.0.0.0/8", "192.168.1.77/24", "0.0.0.0/0", "::/0", "::1", "1::", "2001:db8::8:800:200c:417a/64",
+                  "2001:db8:0:1:1:1:1:1", "1:0:0:2:0:0:0:3", "1:0:0:2:0:0:3:4", "fe80::1:2/10"].iter() {
+            let lit = IpLiteral::parse(s);
+            let ip = IPAddress::parse(*s).unwrap();
+            assert_eq!(lit.to_string(), ip.to_string());
+            assert_eq!(lit.address().to_string(), ip.to_s());
+        }
+    }
+
+    #[test]
+    pub fn test_network_math() {
+        let net = IpLiteral::parse("172.16.10.1/22");
+        assert_eq!(net.network(), IpLiteral::parse("172.16.8.0/22"));
+        assert_eq!(net.broadcast(), IpLiteral::parse("172.16.11.255/22"));
+        assert!(net.contains(&IpLiteral::parse("172.16.9.0/24")));
+        assert!(!net.contains(&IpLiteral::parse("172.16.0.0/16")));
+        assert!(!net.contains(&IpLiteral::parse("::ac10:901")));
+        assert!(IpLiteral::parse("::/0").contains(&IpLiteral::parse("2001:db8::1")));
+        assert_eq!(IpLiteral::parse("::/0").broadcast().address().to_string(),
+                   "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff");
+
+        let subnets = net.subnets(24).unwrap().map(|i| i.to_string()).collect::<Vec<_>>();
+        assert_eq!(subnets, ["172.16.8.0/24", "172.16.9.0/24", "172.16.10.0/24", "172.16.11.0/24"]);
+        assert_eq!(IpLiteral::parse("0.0.0.0/0").subnets(1).unwrap().count(), 2);
+        assert_eq!(IpLiteral::parse("::/0").subnets(0).unwrap().count(), 1);
+        assert_eq!(IpLiteral::parse("ffff::/16").subnets(18).unwrap().last(), Some(IpLiteral::parse("ffff:c000::/18")));
+        assert!(net.subnets(21).is_err());
+        assert!(net.subnets(33).is_err());
+    }
+
+    #[test]
+    pub fn test_classification() {
+        for s in ["10.1.2.3", "172.31.0.1", "192.168.0.1", "127.0.0.1", "8.8.8.8", "0.0.0.0",
+                  "fd00::1", "fc00::1", "::1", "::", "2001:db8::1"].iter() {
+            let lit = IpLiteral::parse(s);
+            let ip = IPAddress::parse(*s).unwrap();
+            assert_eq!(lit.is_private(), ip.is_private(), "{}", s);
+            assert_eq!(lit.is_loopback(), ip.is_loopback(), "{}", s);
+            assert_eq!(lit.is_unspecified(), ip.is_unspecified(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+extern crate ipaddress;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::netmask::{self, NetmaskSyntax};
+
+    fn parse(s: &str) -> String {
+        IPAddress::parse(s).unwrap().to_string()
+    }
+
+    #[test]
+    pub fn test_space_separated() {
+        assert_eq!(parse("10.0.0.1 255.255.255.0"), "10.0.0.1/24");
+        assert_eq!(parse(" 10.0.0.1 mask 255.255.0.0 "), "10.0.0.1/16");
+        assert_eq!(parse("10.0.0.1 netmask 0xffffff00"), "10.0.0.1/24");
+        assert_eq!(parse("10.0.0.1 24"), "10.0.0.1/24");
+        assert_eq!(parse("2001:db8::1 64"), "2001:db8::1/64");
+        assert_eq!(IPAddress::split_at_slash(&String::from("10.0.0.1 mask 255.0.0.0")),
+                   (String::from("10.0.0.1"), Some(String::from("255.0.0.0"))));
+        assert!(IPAddress::parse("10.0.0.1 mask").is_err());
+        assert!(IPAddress::parse("10.0.0.1 255.255.255.0 x").is_err());
+    }
+
+    #[test]
+    pub fn test_one_separator() {
+        assert!(IPAddress::parse("10.0.0.1 /24").is_err());
+        assert!(IPAddress::parse("10.0.0.1/ 24").is_err());
+        assert!(IPAddress::parse("10.0.0.1  24").is_err());
+        assert!(IPAddress::parse("10.0.0.1\t24").is_err());
+        assert!(IPAddress::parse("10.0.0.1\tmask\t255.0.0.0").is_err());
+        assert!(IPAddress::parse("10.0.0.1  mask 255.0.0.0").is_err());
+    }
+
+    #[test]
+    pub fn test_leading_zeros() {
+        assert_eq!(IPAddress::parse("10.0.0.1/00000018").unwrap_err(),
+                   "prefix length 00000018 has leading zeros");
+        assert!(IPAddress::parse("10.0.0.1/024").is_err());
+        assert!(IPAddress::parse("10.0.0.1 024").is_err());
+        assert!(IPAddress::parse("2001:db8::1/064").is_err());
+        assert_eq!(parse("10.0.0.1/0"), "10.0.0.1/0");
+        assert!(IPAddress::parse_netmask_to_prefix("08").is_err());
+    }
+
+    #[test]
+    pub fn test_mapped_space_separated() {
+        let ip = IPAddress::parse("::ffff:10.0.0.1 96").unwrap();
+        assert_eq!(ip.to_string(), "::ffff:a00:1/96");
+        assert!(ip.is_mapped());
+        assert_eq!(parse("::ffff:10.0.0.1 120"), "::ffff:a00:1/120");
+        assert_eq!(parse("::ffff:10.0.0.1 mask ffff:ffff:ffff:ffff:ffff:ffff:ffff:ff00"),
+                   "::ffff:a00:1/120");
+        assert!(IPAddress::parse("::ffff:10.0.0.1 129").is_err());
+        assert_eq!(IPAddress::parse("::ffff:10.0.0.1 255.255.255.0").unwrap_err(),
+                   "dotted netmask is not valid for IPv6");
+        assert_eq!(parse("::ffff:10.0.0.1/24"), "::ffff:a00:1/120");
+    }
+
+    #[test]
+    pub fn test_ipv6_dotted() {
+        assert_eq!(IPAddress::parse("2001:db8::1/255.255.0.0").unwrap_err(),
+                   "Invalid Netmask 2001:db8::1/255.255.0.0: dotted netmask is not valid for IPv6");
+        assert!(IPAddress::parse("2001:db8::1 255.255.0.0").unwrap_err()
+                .ends_with("dotted netmask is not valid for IPv6"));
+        assert_eq!(IPAddress::parse_netmask_to_prefix_for("255.255.0.0", 128).unwrap_err(),
+                   "dotted netmask is not valid for IPv6");
+    }
+
+    #[test]
+    pub fn test_hex() {
+        assert_eq!(parse("10.0.0.1/0xffffff00"), "10.0.0.1/24");
+        assert_eq!(parse("10.0.0.1/0XFFF00000"), "10.0.0.1/12");
+        assert_eq!(parse("10.0.0.1/ffff0000"), "10.0.0.1/16");
+        assert_eq!(parse("10.0.0.1/0x0"), "10.0.0.1/0");
+        assert!(IPAddress::parse("10.0.0.1/0xff").is_err());
+        assert_eq!(parse("2001:db8::1/ffffffffffffffff0000000000000000"), "2001:db8::1/64");
+        assert!(IPAddress::parse("10.0.0.1/0xff00ff00").is_err());
+        assert!(IPAddress::parse("10.0.0.1/0x1ffffff00").is_err());
+        assert_eq!(IPAddress::parse_netmask_to_prefix("0xfffffffc").unwrap(), 30);
+        assert_eq!(IPAddress::parse_netmask_to_prefix("fffffffc").unwrap(), 30);
+        assert_eq!(netmask::hex_to_prefix("ffffff00", Some(32)), Some(Ok(24)));
+        assert_eq!(netmask::hex_to_prefix("0x0", Some(128)), Some(Ok(0)));
+        assert!(netmask::hex_to_prefix("0xffff", Some(128)).unwrap().is_err());
+        assert_eq!(netmask::hex_to_prefix("ff00", Some(32)), None);
+        assert_eq!(netmask::hex_to_prefix("24", None), None);
+        assert_eq!(netmask::hex_to_prefix("0xf0f0f0f0", None), Some(Err(String::from("this is not a net mask 0xf0f0f0f0"))));
+    }
+
+    #[test]
+    pub fn test_ipv6_textual() {
+        assert_eq!(parse("2001:db8::1/ffff:ffff:ffff:ffff::"), "2001:db8::1/64");
+        assert_eq!(parse("2001:db8::1 ffff:ffff:ffff:fff0::"), "2001:db8::1/60");
+        assert_eq!(IPAddress::parse_netmask_to_prefix("ffff:ffff::").unwrap(), 32);
+        assert!(IPAddress::parse("2001:db8::1/ffff:0:ffff::").is_err());
+        assert!(IPAddress::parse("10.0.0.1/ffff:ffff::").is_err());
+        assert!(IPAddress::parse_netmask_to_prefix_for("255.255.0.0", 128).is_err());
+    }
+
+    #[test]
+    pub fn test_strict() {
+        let strict = NetmaskSyntax::Strict;
+        assert_eq!(IPAddress::parse_with("10.0.0.1/24", strict).unwrap().to_string(), "10.0.0.1/24");
+        assert_eq!(IPAddress::parse_with("10.0.0.1/255.0.0.0", strict).unwrap().to_string(), "10.0.0.1/8");
+        assert_eq!(IPAddress::parse_with("2001:db8::1/64", strict).unwrap().to_string(), "2001:db8::1/64");
+        assert!(IPAddress::parse_with("10.0.0.1", strict).is_ok());
+        assert_eq!(IPAddress::parse_with("10.0.0.1 255.0.0.0", strict).unwrap_err(), "Invalid IP 10.0.0.1 255.0.0.0");
+        assert_eq!(IPAddress::parse_with("10.0.0.1/0xff000000", strict).unwrap_err(), "Invalid Netmask 10.0.0.1/0xff000000");
+        assert!(IPAddress::parse_with("2001:db8::1/ffff::", strict).is_err());
+        assert!(IPAddress::parse_with("10.0.0.1/", strict).is_err());
+        assert!(IPAddress::parse_with("10.0.0.1 255.0.0.0", NetmaskSyntax::default()).is_ok());
+    }
+}
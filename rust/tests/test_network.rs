@@ -0,0 +1,81 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::network::{Ipv4Network, Ipv6Network, Network};
+    use std::convert::TryFrom;
+
+    #[test]
+    pub fn test_ipv4_network() {
+        let net = Ipv4Network::parse("192.168.1.77/24").unwrap();
+        assert_eq!(net.address(), 3232235853);
+        assert_eq!(net.octets(), [192, 168, 1, 77]);
+        assert_eq!(net.prefix_len(), 24);
+        assert_eq!(net.netmask(), 0xffffff00);
+        assert_eq!(net.network().address(), 0xc0a80100);
+        assert_eq!(net.broadcast().as_ipaddress().to_s(), "192.168.1.255");
+        assert!(net.is_class_c() && !net.is_class_a());
+        assert!(net.is_private() && !net.is_loopback());
+        assert!(net.network().includes(&Ipv4Network::new(0xc0a80102, 32).unwrap()));
+        assert_eq!(net.to_string(), "192.168.1.77/24");
+        assert_eq!("10.0.0.1/8".parse::<Ipv4Network>().unwrap().address(), 0x0a000001);
+        assert!(Ipv4Network::parse("::1").is_err());
+        assert!(Ipv4Network::new(1, 33).is_err());
+    }
+
+    #[test]
+    pub fn test_ipv6_network() {
+        let net = Ipv6Network::parse("2001:db8::8:800:200c:417a/64").unwrap();
+        assert_eq!(net.address(), 0x20010db8000000000008080020 << 24 | 0x0c417a);
+        assert_eq!(net.segments(), [0x2001, 0xdb8, 0, 0, 8, 0x800, 0x200c, 0x417a]);
+        assert_eq!(net.octets()[..4], [0x20, 0x01, 0x0d, 0xb8]);
+        assert_eq!(net.netmask(), !0u128 << 64);
+        assert_eq!(net.network().to_string(), "2001:db8::/64");
+        assert!(!net.is_mapped());
+        assert!(net.to_ipv4().is_none());
+        assert!(Ipv6Network::parse("fd00::1").unwrap().is_private());
+        assert!(Ipv6Network::parse("10.0.0.1").is_err());
+        assert!(Ipv6Network::new(1, 129).is_err());
+    }
+
+    #[test]
+    pub fn test_mapped() {
+        let net = Ipv4Network::parse("172.16.10.1/24").unwrap();
+        let mapped = net.to_ipv6_mapped();
+        assert!(mapped.is_mapped());
+        assert_eq!(mapped.prefix_len(), 120);
+        assert_eq!(mapped.address(), 0xffff_ac10_0a01);
+        let back = Ipv6Network::parse("::ffff:172.16.10.1").unwrap().to_ipv4().unwrap();
+        assert_eq!(back.as_ipaddress().to_s(), "172.16.10.1");
+    }
+
+    #[test]
+    pub fn test_conversions() {
+        let ip = IPAddress::parse("10.0.0.1/8").unwrap();
+        let net = Ipv4Network::try_from(ip.clone()).unwrap();
+        let back: IPAddress = net.clone().into();
+        assert_eq!(back, ip);
+        assert_eq!(net.as_ipaddress(), &ip);
+        assert_eq!(AsRef::<IPAddress>::as_ref(&net), &ip);
+        assert_eq!(Ipv6Network::try_from(ip.clone()).unwrap_err(), "10.0.0.1/8 is not an IPv6 address");
+
+        let any = Network::try_from(ip.clone()).unwrap();
+        assert!(any.is_ipv4());
+        assert_eq!(any, Network::V4(net.clone()));
+        assert_eq!(Ipv4Network::try_from(any.clone()).unwrap(), net);
+        assert!(Ipv6Network::try_from(any.clone()).is_err());
+        let ip2: IPAddress = any.into();
+        assert_eq!(ip2, ip);
+
+        let ip6 = IPAddress::parse("2001:db8::1/64").unwrap();
+        let any6: Network = "2001:db8::1/64".parse().unwrap();
+        assert!(any6.is_ipv6());
+        assert_eq!(any6.as_ipaddress(), &ip6);
+        assert_eq!(any6.as_ipaddress().size(), ip6.size());
+        let mut list = [any6, Network::try_from(ip).unwrap()];
+        list.sort();
+        assert!(list[0].is_ipv4());
+    }
+}
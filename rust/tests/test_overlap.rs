@@ -0,0 +1,66 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::overlap;
+
+    fn parse(nets: Vec<&str>) -> Vec<IPAddress> {
+        IPAddress::to_ipaddress_vec(nets).unwrap()
+    }
+
+    fn describe(report: &overlap::Report) -> Vec<String> {
+        report.overlaps.iter().map(|i| {
+            format!("{}:{} {:?} {}:{} = {}", i.a.list, i.a.network.to_string(), i.relation,
+                    i.b.list, i.b.network.to_string(), i.intersection.to_string())
+        }).collect()
+    }
+
+    #[test]
+    pub fn test_between_lists() {
+        let ours = parse(vec!["10.0.0.0/16", "192.168.0.0/24"]);
+        let theirs = parse(vec!["10.1.0.0/16", "10.0.1.0/24", "192.168.0.128/25", "172.16.0.0/12"]);
+        let report = overlap::analyze(&[ours, theirs]);
+        assert_eq!(describe(&report),
+                   ["0:10.0.0.0/16 Contains 1:10.0.1.0/24 = 10.0.1.0/24",
+                    "0:192.168.0.0/24 Contains 1:192.168.0.128/25 = 192.168.0.128/25"]);
+        assert_eq!(report.overlaps[0].b.index, 1);
+        assert!(report.duplicates().is_empty());
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    pub fn test_within_one_list() {
+        let nets = parse(vec!["10.0.1.0/24", "10.0.0.0/8", "10.0.1.0/24", "10.0.1.64/26", "2001:db8::/32",
+                              "2001:db8:1::/48", "::ffff:10.0.1.1"]);
+        let report = overlap::analyze(&[nets]);
+        assert_eq!(describe(&report),
+                   ["0:10.0.1.0/24 Within 0:10.0.0.0/8 = 10.0.1.0/24",
+                    "0:10.0.0.0/8 Contains 0:10.0.1.0/24 = 10.0.1.0/24",
+                    "0:10.0.1.0/24 Equal 0:10.0.1.0/24 = 10.0.1.0/24",
+                    "0:10.0.0.0/8 Contains 0:10.0.1.64/26 = 10.0.1.64/26",
+                    "0:10.0.1.0/24 Contains 0:10.0.1.64/26 = 10.0.1.64/26",
+                    "0:10.0.1.0/24 Contains 0:10.0.1.64/26 = 10.0.1.64/26",
+                    "0:2001:db8::/32 Contains 0:2001:db8:1::/48 = 2001:db8:1::/48"]);
+        let duplicates = report.duplicates();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!((duplicates[0].a.index, duplicates[0].b.index), (0, 2));
+    }
+
+    #[test]
+    pub fn test_unaligned() {
+        let report = overlap::analyze(&[parse(vec!["10.0.0.5/24", "10.0.1.0/24"]),
+                                        parse(vec!["10.0.0.200/25"])]);
+        assert_eq!(report.unaligned.iter().map(|i| (i.list, i.index)).collect::<Vec<_>>(), [(0, 0), (1, 0)]);
+        assert_eq!(describe(&report), ["0:10.0.0.5/24 Contains 1:10.0.0.200/25 = 10.0.0.128/25"]);
+    }
+
+    #[test]
+    pub fn test_clean() {
+        let report = overlap::analyze(&[parse(vec!["10.0.0.0/24", "10.0.1.0/24"]), parse(vec!["::/0"])]);
+        assert!(report.is_clean());
+        let empty: Vec<Vec<IPAddress>> = vec![];
+        assert!(overlap::analyze(&empty).is_clean());
+    }
+}
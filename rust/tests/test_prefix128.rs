@@ -7,6 +7,10 @@ extern crate num;
 #[cfg(test)]
 mod tests {
     use ipaddress::prefix128;
+    use ipaddress::prefix32;
+    use ipaddress::prefix::Prefix;
+    use ipaddress::ip_bits::IpVersion;
+    use ipaddress::IPAddress;
     use std::collections::HashMap;
     use num::bigint::BigUint;
 
@@ -67,4 +71,33 @@ mod tests {
             assert_eq!(u128, prefix128::new(num).unwrap().netmask())
         }
     }
+    #[test]
+    pub fn test_masks() {
+        let prefix = prefix128::new(64).unwrap();
+        assert_eq!(!0u128 << 64, prefix.netmask_u128());
+        assert_eq!(!0u128 >> 64, prefix.host_mask_u128());
+        assert_eq!(0, prefix128::new(0).unwrap().netmask_u128());
+        assert_eq!(!0u128, prefix128::new(0).unwrap().host_mask_u128());
+        assert_eq!(!0u128, prefix128::new(128).unwrap().netmask_u128());
+        let mask = IPAddress::parse("ffff:ffff:ffff:ffff::").unwrap();
+        assert_eq!(prefix, Prefix::from_netmask(&mask).unwrap());
+        let wildcard = IPAddress::parse("::ffff:ffff:ffff:ffff").unwrap();
+        assert_eq!(prefix, Prefix::from_wildcard(&wildcard).unwrap());
+        assert_eq!(prefix, "ffff:ffff:ffff:ffff::".parse::<Prefix>().unwrap());
+        assert_eq!("64", prefix.to_string());
+        assert_eq!("ffff:ffff:ffff:ffff::", prefix.to_ip_str());
+        for num in 0..129 {
+            let prefix = prefix128::new(num).unwrap();
+            assert_eq!(prefix, Prefix::parse(IpVersion::V6, &prefix.to_string()).unwrap());
+        }
+        assert_eq!(prefix, Prefix::parse(IpVersion::V6, "/64").unwrap());
+        assert_eq!(prefix, Prefix::parse(IpVersion::V6, "ffff:ffff:ffff:ffff::").unwrap());
+        assert!(Prefix::parse(IpVersion::V6, "255.255.0.0").is_err());
+        assert!(Prefix::parse(IpVersion::V6, "129").is_err());
+        assert!("64".parse::<Prefix>().is_err());
+        assert!(prefix < prefix128::new(65).unwrap());
+        assert!(prefix32::new(32).unwrap() < prefix);
+        assert!(prefix.sub(65).is_err());
+        assert!(prefix.add(65).is_err());
+    }
 }
@@ -7,6 +7,7 @@ extern crate num_traits;
 mod tests {
     use ipaddress::IPAddress;
     use ipaddress::prefix32;
+    use ipaddress::prefix::Prefix;
     use ipaddress::ipv4;
     use std::collections::HashMap;
     // use num::bigint::BigUint;
@@ -127,9 +128,11 @@ mod tests {
     pub fn test_method_minus() {
         let p1 = prefix32::new(8).unwrap();
         let p2 = prefix32::new(24).unwrap();
-        assert_eq!(16, p1.sub_prefix(&p2).unwrap().num);
+        assert_eq!("Prefix 8 - 24 underflows", p1.sub_prefix(&p2).unwrap_err());
         assert_eq!(16, p2.sub_prefix(&p1).unwrap().num);
         assert_eq!(20, p2.sub(4).unwrap().num);
+        assert!(p2.add(9).is_err());
+        assert!(p2.add(usize::MAX).is_err());
     }
     #[allow(dead_code)]
     #[allow(unused_attributes)]
@@ -144,7 +147,7 @@ mod tests {
     pub fn test_method_octets() {
         for (arr, pref) in setup().octets_hash {
             let prefix = prefix32::new(pref).unwrap();
-            assert_eq!(prefix.ip_bits.parts(&prefix.netmask()), arr);
+            assert_eq!(prefix.ip_bits().parts(&prefix.netmask()), arr);
         }
     }
     #[allow(dead_code)]
@@ -155,7 +158,7 @@ mod tests {
             let prefix = prefix32::new(pref).unwrap();
             for index in 0..arr.len() {
                 let oct = arr.get(index);
-                assert_eq!(prefix.ip_bits.parts(&prefix.netmask()).get(index), oct)
+                assert_eq!(prefix.ip_bits().parts(&prefix.netmask()).get(index), oct)
             }
         }
     }
@@ -167,4 +170,44 @@ mod tests {
         assert_eq!("0.255.255.255",
                    ipv4::from_u32(prefix.host_mask().to_u32().unwrap(), 0).unwrap().to_s());
     }
+    #[test]
+    pub fn test_masks() {
+        let prefix = prefix32::new(20).unwrap();
+        let copy = prefix;
+        assert_eq!(copy, prefix);
+        assert_eq!(0xfffff000, prefix.netmask_u128());
+        assert_eq!(0xfff, prefix.host_mask_u128());
+        assert_eq!(0, prefix32::new(0).unwrap().netmask_u128());
+        assert_eq!(0xffffffff, prefix32::new(0).unwrap().host_mask_u128());
+        assert_eq!(0xffffffff, prefix32::new(32).unwrap().netmask_u128());
+        assert_eq!(0, prefix32::new(32).unwrap().host_mask_u128());
+    }
+    #[test]
+    pub fn test_from_netmask_and_wildcard() {
+        let mask = IPAddress::parse("255.255.240.0").unwrap();
+        assert_eq!(prefix32::new(20).unwrap(), Prefix::from_netmask(&mask).unwrap());
+        let wildcard = IPAddress::parse("0.0.15.255").unwrap();
+        assert_eq!(prefix32::new(20).unwrap(), Prefix::from_wildcard(&wildcard).unwrap());
+        assert_eq!(32, Prefix::from_wildcard(&IPAddress::parse("0.0.0.0").unwrap()).unwrap().num);
+        assert!(Prefix::from_netmask(&IPAddress::parse("255.0.255.0").unwrap()).is_err());
+        assert_eq!("this is not a wildcard mask 16711935",
+                   Prefix::from_wildcard(&IPAddress::parse("0.255.0.255").unwrap()).unwrap_err());
+    }
+    #[test]
+    pub fn test_display_from_str() {
+        let prefix = prefix32::new(24).unwrap();
+        assert_eq!("24", prefix.to_string());
+        assert_eq!("24", prefix.to_s());
+        for num in 0..33 {
+            let prefix = prefix32::new(num).unwrap();
+            assert_eq!(prefix, prefix.to_string().parse::<Prefix>().unwrap());
+        }
+        assert_eq!(prefix, "/24".parse::<Prefix>().unwrap());
+        assert_eq!(prefix, "255.255.255.0".parse::<Prefix>().unwrap());
+        assert!("255.0.255.0".parse::<Prefix>().is_err());
+        assert!("33".parse::<Prefix>().is_err());
+        assert!("024".parse::<Prefix>().is_err());
+        assert!("".parse::<Prefix>().is_err());
+        assert!("/".parse::<Prefix>().is_err());
+    }
 }
@@ -0,0 +1,64 @@
+#![cfg(feature = "rand")]
+extern crate ipaddress;
+extern crate num;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use rand::{SeedableRng, XorShiftRng};
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([1, 2, 3, 4])
+    }
+
+    #[test]
+    pub fn test_random_host() {
+        let mut rng = rng();
+        let ip = IPAddress::parse("10.0.0.0/30").unwrap();
+        for _ in 0..50 {
+            let host = ip.random_host(&mut rng).unwrap();
+            assert!(host.to_s() == "10.0.0.1" || host.to_s() == "10.0.0.2");
+            assert_eq!(host.prefix.num, 30);
+        }
+        assert!(IPAddress::parse("10.0.0.0/31").unwrap().random_host(&mut rng).is_none());
+        let ip6 = IPAddress::parse("2001:db8::/3").unwrap();
+        for _ in 0..50 {
+            assert!(ip6.includes(&ip6.random_host(&mut rng).unwrap()));
+        }
+    }
+
+    #[test]
+    pub fn test_random_subnet() {
+        let mut rng = rng();
+        let ip = IPAddress::parse("10.1.2.3/8").unwrap();
+        for _ in 0..50 {
+            let net = ip.random_subnet(24, &mut rng).unwrap();
+            assert_eq!(net.prefix.num, 24);
+            assert!(net.is_network());
+            assert!(ip.includes(&net));
+        }
+        assert_eq!(ip.random_subnet(8, &mut rng).unwrap().to_string(), "10.0.0.0/8");
+        assert!(ip.random_subnet(7, &mut rng).is_err());
+        assert!(ip.random_subnet(33, &mut rng).is_err());
+        let net = IPAddress::parse("::/0").unwrap().random_subnet(120, &mut rng).unwrap();
+        assert_eq!(net.prefix.num, 120);
+    }
+
+    #[test]
+    pub fn test_random_hosts() {
+        let mut rng = rng();
+        let ip = IPAddress::parse("192.168.0.0/29").unwrap();
+        let all = ip.random_hosts(6, &mut rng).unwrap();
+        assert_eq!(IPAddress::to_s_vec(&all),
+                   ["192.168.0.1", "192.168.0.2", "192.168.0.3", "192.168.0.4", "192.168.0.5", "192.168.0.6"]);
+        let some = ip.random_hosts(3, &mut rng).unwrap();
+        assert_eq!(some.len(), 3);
+        assert!(some.windows(2).all(|i| i[0] < i[1]));
+        assert!(ip.random_hosts(7, &mut rng).is_err());
+        assert_eq!(ip.random_hosts(0, &mut rng).unwrap().len(), 0);
+
+        let ip6 = IPAddress::parse("2001:db8::/32").unwrap();
+        assert_eq!(ip6.random_hosts(1000, &mut rng).unwrap().len(), 1000);
+    }
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "serde")]
+extern crate ipaddress;
+extern crate serde_test;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::prefix::Prefix;
+    use ipaddress::{prefix128, prefix32};
+    use ipaddress::family;
+    use serde_test::{assert_de_tokens_error, assert_ser_tokens_error, assert_tokens, Configure, Token};
+
+    #[test]
+    pub fn test_readable() {
+        assert_tokens(&IPAddress::parse("10.0.0.0/8").unwrap().readable(), &[Token::Str("10.0.0.0/8")]);
+        assert_tokens(&IPAddress::parse("2001:db8::1/64").unwrap().readable(), &[Token::Str("2001:db8::1/64")]);
+        assert_tokens(&IPAddress::parse("::ffff:10.0.1.1").unwrap().readable(),
+                      &[Token::Str("::ffff:a00:101/128")]);
+        assert_de_tokens_error::<serde_test::Readable<IPAddress>>(&[Token::Str("10.0.0.256")], "Invalid IP 10.0.0.256");
+    }
+
+    #[test]
+    pub fn test_compact() {
+        assert_tokens(&IPAddress::parse("10.0.0.0/8").unwrap().compact(),
+                      &[Token::Tuple { len: 3 }, Token::U8(4), Token::Bytes(&[10, 0, 0, 0]), Token::U8(8),
+                        Token::TupleEnd]);
+        assert_tokens(&IPAddress::parse("::1").unwrap().compact(),
+                      &[Token::Tuple { len: 3 }, Token::U8(6),
+                        Token::Bytes(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]), Token::U8(128),
+                        Token::TupleEnd]);
+        let mapped = IPAddress::parse("::ffff:10.0.0.1").unwrap();
+        assert_tokens(&mapped.clone().compact(),
+                      &[Token::Tuple { len: 3 }, Token::U8(6),
+                        Token::Bytes(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 10, 0, 0, 1]), Token::U8(128),
+                        Token::TupleEnd]);
+        assert_eq!(mapped.mapped.unwrap().to_string(), "10.0.0.1/32");
+        assert_de_tokens_error::<serde_test::Compact<IPAddress>>(
+            &[Token::Tuple { len: 3 }, Token::U8(4), Token::Bytes(&[10, 0, 0, 0]), Token::U8(33)],
+            "Prefix must be in range 0..32, got: 33");
+        assert_de_tokens_error::<serde_test::Compact<IPAddress>>(
+            &[Token::Tuple { len: 3 }, Token::U8(6), Token::Bytes(&[10, 0, 0, 0]), Token::U8(8)],
+            "invalid length 4, expected 4 octets for IPv4, 16 for IPv6");
+        assert_de_tokens_error::<serde_test::Compact<IPAddress>>(
+            &[Token::Tuple { len: 3 }, Token::U8(5), Token::Bytes(&[10, 0, 0, 0]), Token::U8(8)],
+            "unknown IP version 5");
+    }
+
+    #[test]
+    pub fn test_prefix() {
+        let prefix: Prefix = prefix32::new(24).unwrap();
+        assert_tokens(&prefix.readable(),
+                      &[Token::Struct { name: "Prefix", len: 2 }, Token::Str("version"), Token::U8(4),
+                        Token::Str("num"), Token::U8(24), Token::StructEnd]);
+        assert_tokens(&prefix128::new(64).unwrap().compact(),
+                      &[Token::Tuple { len: 2 }, Token::U8(6), Token::U8(64), Token::TupleEnd]);
+        assert_de_tokens_error::<serde_test::Compact<Prefix>>(
+            &[Token::Tuple { len: 2 }, Token::U8(6), Token::U8(129)],
+            "Prefix must be in range 0..128, got: 129");
+    }
+
+    #[test]
+    pub fn test_custom_family() {
+        let mac = family::parse(&family::EUI48, "00:1b:21:3a:4f:5c").unwrap();
+        assert_ser_tokens_error(&mac.clone().readable(), &[], "can not serialize a EUI-48 address");
+        assert_ser_tokens_error(&mac.compact(), &[], "can not serialize a EUI-48 address");
+        assert_ser_tokens_error(&family::prefix(&family::EUI48, 24).unwrap().compact(), &[],
+                                "can not serialize a EUI-48 address");
+    }
+}
@@ -0,0 +1,77 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::host_policy::HostPolicy;
+    use std::collections::HashSet;
+
+    fn sorted(mut v: Vec<IPAddress>) -> Vec<String> {
+        v.sort();
+        IPAddress::to_s_vec(&v)
+    }
+
+    #[test]
+    pub fn test_full_cycle() {
+        let ip = IPAddress::parse("10.0.0.77/24").unwrap();
+        let visited = ip.shuffled_iter(7).collect::<Vec<_>>();
+        assert_eq!(visited.len(), 256);
+        let all = (0..256).map(|i| format!("10.0.0.{}", i)).collect::<Vec<_>>();
+        assert_eq!(sorted(visited.clone()), all);
+        assert!(visited.iter().all(|i| i.prefix.num == 24));
+        assert_ne!(IPAddress::to_s_vec(&visited), all);
+
+        assert_eq!(IPAddress::to_s_vec(&IPAddress::parse("10.0.0.1/32").unwrap().shuffled_iter(7).collect()),
+                   ["10.0.0.1"]);
+        let ip6 = IPAddress::parse("2001:db8::/118").unwrap();
+        let set = ip6.shuffled_iter(1).map(|i| i.host_address).collect::<HashSet<_>>();
+        assert_eq!(set.len(), 1024);
+    }
+
+    #[test]
+    pub fn test_seeded() {
+        let ip = IPAddress::parse("10.0.0.0/29").unwrap();
+        assert_eq!(ip.shuffled_iter(42).collect::<Vec<_>>(), ip.shuffled_iter(42).collect::<Vec<_>>());
+        assert_ne!(ip.shuffled_iter(42).collect::<Vec<_>>(), ip.shuffled_iter(43).collect::<Vec<_>>());
+        let ip6 = IPAddress::parse("2001:db8::/32").unwrap();
+        let first = ip6.shuffled_iter(42).take(3).collect::<Vec<_>>();
+        assert_eq!(first, ip6.shuffled_iter(42).take(3).collect::<Vec<_>>());
+        assert!(first.iter().all(|i| ip6.includes(i)));
+    }
+
+    #[test]
+    pub fn test_hosts() {
+        let ip = IPAddress::parse("192.168.1.0/28").unwrap();
+        let hosts = ip.shuffled_hosts_iter(3, &HostPolicy::Classic).collect::<Vec<_>>();
+        assert_eq!(hosts.len(), 14);
+        let all = (1..15).map(|i| format!("192.168.1.{}", i)).collect::<Vec<_>>();
+        assert_eq!(sorted(hosts), all);
+        let hosts = ip.shuffled_hosts_iter(3, &HostPolicy::Reserved { head: 4, tail: 1 }).count();
+        assert_eq!(hosts, 11);
+    }
+
+    #[test]
+    pub fn test_shards() {
+        let ip = IPAddress::parse("172.16.0.0/26").unwrap();
+        let mut all = Vec::new();
+        for shard in 0..3 {
+            all.extend(ip.shuffled_iter(9).shard(shard, 3).unwrap());
+        }
+        assert_eq!(all.len(), 64);
+        assert_eq!(sorted(all), sorted(ip.shuffled_iter(9).collect()));
+        assert!(ip.shuffled_iter(9).shard(3, 3).is_err());
+        assert!(ip.shuffled_iter(9).shard(0, 0).is_err());
+    }
+
+    #[test]
+    pub fn test_resume() {
+        let ip = IPAddress::parse("172.16.0.0/26").unwrap();
+        let expected = ip.shuffled_iter(5).shard(1, 2).unwrap().collect::<Vec<_>>();
+        let mut it = ip.shuffled_iter(5).shard(1, 2).unwrap();
+        let mut visited = it.by_ref().take(10).collect::<Vec<_>>();
+        let saved = it.position();
+        visited.extend(ip.shuffled_iter(5).shard(1, 2).unwrap().seek(saved));
+        assert_eq!(visited, expected);
+    }
+}
@@ -0,0 +1,112 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::host_policy::HostPolicy;
+    use ipaddress::subnet_info;
+    use ipaddress::subnet_info::SubnetInfo;
+    use num::bigint::BigUint;
+    use num::FromPrimitive;
+
+    fn info(s: &str) -> SubnetInfo {
+        SubnetInfo::new(&IPAddress::parse(s).unwrap())
+    }
+
+    #[test]
+    pub fn test_ipv4() {
+        let info = info("192.168.1.10/24");
+        assert_eq!(info.network.to_string(), "192.168.1.0/24");
+        assert_eq!(info.netmask.to_s(), "255.255.255.0");
+        assert_eq!(info.wildcard.to_s(), "0.0.0.255");
+        assert_eq!(info.broadcast.unwrap().to_s(), "192.168.1.255");
+        assert_eq!(info.first_host.unwrap().to_s(), "192.168.1.1");
+        assert_eq!(info.last_host.unwrap().to_s(), "192.168.1.254");
+        assert_eq!(info.host_count, BigUint::from_u32(254).unwrap());
+        assert_eq!(info.size, BigUint::from_u32(256).unwrap());
+        assert_eq!(info.class, Some('C'));
+        assert_eq!(info.special_purpose, Some("Private-Use (RFC 1918)"));
+        assert_eq!(info.reverse_zones, ["1.168.192.in-addr.arpa"]);
+        assert_eq!(info.binary, "11000000.10101000.00000001.00001010");
+        assert_eq!(info.hex, "c0a8010a");
+    }
+
+    #[test]
+    pub fn test_ipv6() {
+        let info = info("2001:db8::1/64");
+        assert_eq!(info.network.to_string(), "2001:db8::/64");
+        assert_eq!(info.netmask.to_s(), "ffff:ffff:ffff:ffff::");
+        assert_eq!(info.wildcard.to_s(), "::ffff:ffff:ffff:ffff");
+        assert!(info.broadcast.is_none());
+        assert_eq!(info.first_host.unwrap().to_s(), "2001:db8::");
+        assert_eq!(info.class, None);
+        assert_eq!(info.special_purpose, Some("Documentation (RFC 3849)"));
+        assert_eq!(info.reverse_zones, ["0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa"]);
+        assert_eq!(info.hex, "20010db8000000000000000000000001");
+        assert_eq!(info.binary.split(':').count(), 8);
+    }
+
+    #[test]
+    pub fn test_special_purpose() {
+        let special = |s: &str| subnet_info::special_purpose(&IPAddress::parse(s).unwrap());
+        assert_eq!(special("8.8.8.8"), None);
+        assert_eq!(special("127.0.0.1"), Some("Loopback (RFC 1122)"));
+        assert_eq!(special("100.100.0.1"), Some("Shared Address Space (RFC 6598)"));
+        assert_eq!(special("255.255.255.255"), Some("Limited Broadcast (RFC 919)"));
+        assert_eq!(special("250.0.0.1"), Some("Reserved (RFC 1112)"));
+        assert_eq!(special("2001::1"), Some("TEREDO (RFC 4380)"));
+        assert_eq!(special("2001:100::1"), Some("IETF Protocol Assignments (RFC 2928)"));
+        assert_eq!(special("fe80::1"), Some("Link-Local Unicast (RFC 4291)"));
+        assert_eq!(special("2a00::1"), None);
+        assert_eq!(subnet_info::classful(&IPAddress::parse("10.0.0.1").unwrap()), Some('A'));
+        assert_eq!(subnet_info::classful(&IPAddress::parse("172.16.0.1").unwrap()), Some('B'));
+        assert_eq!(subnet_info::classful(&IPAddress::parse("239.0.0.1").unwrap()), Some('D'));
+    }
+
+    #[test]
+    pub fn test_policy() {
+        let ip = IPAddress::parse("10.0.0.0/31").unwrap();
+        let classic = SubnetInfo::new(&ip);
+        assert!(classic.first_host.is_none());
+        assert!(classic.to_text().contains("HostMin:   -\n"));
+        let p2p = SubnetInfo::with_policy(&ip, &HostPolicy::Rfc3021);
+        assert_eq!(p2p.first_host.unwrap().to_s(), "10.0.0.0");
+        assert_eq!(p2p.host_count, BigUint::from_u32(2).unwrap());
+    }
+
+    #[test]
+    pub fn test_to_text() {
+        assert_eq!(info("192.168.1.10/24").to_text(),
+                   "Address:   192.168.1.10\n\
+                    Network:   192.168.1.0/24\n\
+                    Netmask:   255.255.255.0 = 24\n\
+                    Wildcard:  0.0.0.255\n\
+                    Broadcast: 192.168.1.255\n\
+                    HostMin:   192.168.1.1\n\
+                    HostMax:   192.168.1.254\n\
+                    Hosts:     254\n\
+                    Class:     C\n\
+                    Type:      Private-Use (RFC 1918)\n\
+                    Reverse:   1.168.192.in-addr.arpa\n\
+                    Binary:    11000000.10101000.00000001.00001010\n\
+                    Hex:       c0a8010a\n");
+        let text = info("172.17.100.50/15").to_text();
+        assert!(text.contains("Reverse:   16.172.in-addr.arpa\nReverse:   17.172.in-addr.arpa\n"));
+    }
+
+    #[test]
+    pub fn test_to_json() {
+        assert_eq!(info("10.0.0.1/24").to_json(),
+                   "{\"version\":4,\"address\":\"10.0.0.1\",\"prefix\":24,\"network\":\"10.0.0.0/24\",\
+                    \"netmask\":\"255.255.255.0\",\"wildcard\":\"0.0.0.255\",\"broadcast\":\"10.0.0.255\",\
+                    \"first_host\":\"10.0.0.1\",\"last_host\":\"10.0.0.254\",\"size\":\"256\",\"host_count\":\"254\",\
+                    \"class\":\"A\",\"special_purpose\":\"Private-Use (RFC 1918)\",\
+                    \"reverse_zones\":[\"0.0.10.in-addr.arpa\"],\
+                    \"binary\":\"00001010.00000000.00000000.00000001\",\"hex\":\"0a000001\"}");
+        let json = info("2a00::1/128").to_json();
+        assert!(json.contains("\"broadcast\":null"));
+        assert!(json.contains("\"class\":null,\"special_purpose\":null"));
+        assert!(info("::/0").to_json().contains("\"size\":\"340282366920938463463374607431768211456\""));
+    }
+}
@@ -0,0 +1,86 @@
+extern crate ipaddress;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::targets::{Target, Targets};
+
+    fn hosts(spec: &str) -> Vec<String> {
+        Targets::parse(spec).unwrap().hosts().map(|i| i.to_s()).collect()
+    }
+
+    fn networks(spec: &str) -> Vec<String> {
+        Targets::parse(spec).unwrap().to_networks().iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    pub fn test_octet_patterns() {
+        assert_eq!(hosts("10.0.1.[1,5,9-12]"), ["10.0.1.1", "10.0.1.5", "10.0.1.9", "10.0.1.10", "10.0.1.11", "10.0.1.12"]);
+        assert_eq!(hosts("192.168.0-1.3-4"), ["192.168.0.3", "192.168.0.4", "192.168.1.3", "192.168.1.4"]);
+        assert_eq!(hosts("10.0.0.-2"), ["10.0.0.0", "10.0.0.1", "10.0.0.2"]);
+        assert_eq!(hosts("10.0.0.254-"), ["10.0.0.254", "10.0.0.255"]);
+        assert_eq!(hosts("192.168.3-5,7.1"), ["192.168.3.1", "192.168.4.1", "192.168.5.1", "192.168.7.1"]);
+        assert_eq!(hosts("10.0.0.1,5,9"), ["10.0.0.1", "10.0.0.5", "10.0.0.9"]);
+        assert_eq!(hosts("10.0.0.1,5,9-12"), hosts("10.0.0.[1,5,9-12]"));
+        assert_eq!(hosts("10.0.0.1,*").len(), 256);
+        let all = Targets::parse("10.0.0.*").unwrap();
+        assert_eq!(all.hosts().count(), 256);
+        assert_eq!(all.hosts().last().unwrap().to_string(), "10.0.0.255/32");
+        assert_eq!(Targets::parse("192.168.0-3.1-254").unwrap().hosts().count(), 4 * 254);
+    }
+
+    #[test]
+    pub fn test_lists_and_ranges() {
+        assert_eq!(hosts("10.0.0.1 10.0.0.3\t10.0.0.7/31"), ["10.0.0.1", "10.0.0.3", "10.0.0.6", "10.0.0.7"]);
+        assert_eq!(hosts("10.0.0.254-10.0.1.1"), ["10.0.0.254", "10.0.0.255", "10.0.1.0", "10.0.1.1"]);
+        assert_eq!(hosts("2001:db8::fe-2001:db8::101"), ["2001:db8::fe", "2001:db8::ff", "2001:db8::100", "2001:db8::101"]);
+        assert_eq!(hosts("2001:db8::/127"), ["2001:db8::", "2001:db8::1"]);
+    }
+
+    #[test]
+    pub fn test_exclusions() {
+        assert_eq!(hosts("10.0.0.[1,5,9-12] !10.0.0.10"), ["10.0.0.1", "10.0.0.5", "10.0.0.9", "10.0.0.11", "10.0.0.12"]);
+        assert_eq!(hosts("10.0.0.0/29 !10.0.0.1-10.0.0.6"), ["10.0.0.0", "10.0.0.7"]);
+        assert_eq!(hosts("10.0.0.0/29 !10.0.0.*"), Vec::<String>::new());
+        let targets = Targets::parse("10.0.0.0/30").unwrap().exclude("10.0.0.1 10.0.0.2").unwrap();
+        assert_eq!(targets.hosts().map(|i| i.to_s()).collect::<Vec<_>>(), ["10.0.0.0", "10.0.0.3"]);
+        assert!(targets.is_excluded(&IPAddress::parse("10.0.0.1").unwrap()));
+
+        // lazy, the excluded half is skipped in one step
+        let wide = Targets::parse("::/0 !::-7fff:ffff:ffff:ffff:ffff:ffff:ffff:ffff").unwrap();
+        let first: Vec<String> = wide.hosts().take(2).map(|i| i.to_s()).collect();
+        assert_eq!(first, ["8000::", "8000::1"]);
+        let last = Targets::parse("ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/127").unwrap();
+        assert_eq!(last.hosts().count(), 2);
+    }
+
+    #[test]
+    pub fn test_compact() {
+        assert_eq!(networks("10.0.0.[1,5,9-12] !10.0.0.10"),
+                   ["10.0.0.1/32", "10.0.0.5/32", "10.0.0.9/32", "10.0.0.11/32", "10.0.0.12/32"]);
+        assert_eq!(networks("10.0.0-3.*"), ["10.0.0.0/22"]);
+        assert_eq!(networks("10.0.0,2.* 10.0.1.0/24"), ["10.0.0.0/23", "10.0.2.0/24"]);
+        assert_eq!(networks("*.*.*.*"), ["0.0.0.0/0"]);
+        assert_eq!(networks("10.0.0.0/24 !10.0.0.128/25 2001:db8::/64"), ["10.0.0.0/25", "2001:db8::/64"]);
+        assert_eq!(networks("10-11.0.0.1"), ["10.0.0.1/32", "11.0.0.1/32"]);
+
+        let ranges = Targets::parse("10.0.0.1-10.0.0.6 10.0.0.7 10.0.0.20 !10.0.0.3").unwrap().to_ranges();
+        let ranges: Vec<String> = ranges.iter().map(|r| format!("{}-{}", r.0.to_s(), r.1.to_s())).collect();
+        assert_eq!(ranges, ["10.0.0.1-10.0.0.2", "10.0.0.4-10.0.0.7", "10.0.0.20-10.0.0.20"]);
+    }
+
+    #[test]
+    pub fn test_errors() {
+        assert!(Target::parse("10.0.0.256").is_err());
+        assert_eq!(Target::parse("10.0.*").unwrap_err(), "Invalid target 10.0.*");
+        assert_eq!(Target::parse("10.0.0.5-3").unwrap_err(), "Invalid target 10.0.0.5-3");
+        assert_eq!(Target::parse("10.0.0.[1,256]").unwrap_err(), "Invalid target 10.0.0.[1,256]");
+        assert_eq!(Target::parse("10.0.0.5-10.0.0.1").unwrap_err(), "Invalid range 10.0.0.5-10.0.0.1");
+        assert!(Target::parse("10.0.0.1-::1").is_err());
+        assert!(Targets::parse("10.0.0.1 !x").is_err());
+        // commas do not separate targets
+        assert_eq!(Target::parse("10.0.0.1,10.0.0.3").unwrap_err(), "Invalid target 10.0.0.1,10.0.0.3");
+        assert!(Target::parse("10.0.0.1,").is_err());
+        assert_eq!(Targets::parse("").unwrap().hosts().count(), 0);
+    }
+}
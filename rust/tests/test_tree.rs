@@ -0,0 +1,75 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::tree::Forest;
+
+    fn parse(nets: Vec<&str>) -> Vec<IPAddress> {
+        IPAddress::to_ipaddress_vec(nets).unwrap()
+    }
+
+    fn plan() -> Vec<IPAddress> {
+        parse(vec!["10.0.16.0/20", "10.0.1.0/24", "10.0.0.0/16", "10.0.0.0/20", "10.0.1.77/24",
+                   "192.168.0.0/24", "2001:db8::/32", "2001:db8:1::/48"])
+    }
+
+    #[test]
+    pub fn test_build() {
+        let forest = Forest::new(&plan());
+        assert_eq!(forest.roots.iter().map(|i| i.network.to_string()).collect::<Vec<_>>(),
+                   ["10.0.0.0/16", "192.168.0.0/24", "2001:db8::/32"]);
+        let region = &forest.roots[0];
+        assert_eq!(region.children.iter().map(|i| i.network.to_string()).collect::<Vec<_>>(),
+                   ["10.0.0.0/20", "10.0.16.0/20"]);
+        assert_eq!(region.children[0].children.len(), 1);
+        assert_eq!(forest.iter().iter().map(|&(depth, _)| depth).collect::<Vec<_>>(), [0, 1, 2, 1, 0, 0, 1]);
+        assert_eq!(forest.leaves().iter().map(|i| i.network.to_string()).collect::<Vec<_>>(),
+                   ["10.0.1.0/24", "10.0.16.0/20", "192.168.0.0/24", "2001:db8:1::/48"]);
+        assert_eq!(Forest::new(&[]).roots.len(), 0);
+    }
+
+    #[test]
+    pub fn test_utilization() {
+        let forest = Forest::new(&plan());
+        let region = &forest.roots[0];
+        assert_eq!(region.covered().to_string(), "8192");
+        assert_eq!(region.free().to_string(), "57344");
+        assert_eq!(region.utilization(), 12.5);
+        assert_eq!(region.children[0].utilization(), 6.25);
+        assert_eq!(forest.roots[1].utilization(), 0.0);
+    }
+
+    #[test]
+    pub fn test_path_and_find() {
+        let forest = Forest::new(&plan());
+        let ip = IPAddress::parse("10.0.1.5").unwrap();
+        assert_eq!(forest.path(&ip).iter().map(|i| i.network.to_string()).collect::<Vec<_>>(),
+                   ["10.0.0.0/16", "10.0.0.0/20", "10.0.1.0/24"]);
+        assert_eq!(forest.find(&IPAddress::parse("10.0.200.1").unwrap()).unwrap().network.to_string(),
+                   "10.0.0.0/16");
+        assert!(forest.find(&IPAddress::parse("172.16.0.1").unwrap()).is_none());
+    }
+
+    #[test]
+    pub fn test_gaps_and_text() {
+        let forest = Forest::with_gaps(&parse(vec!["10.0.0.0/16", "10.0.0.0/20", "10.0.1.0/24",
+                                                   "10.0.16.0/20"]));
+        assert_eq!(forest.to_text().lines().collect::<Vec<_>>(),
+                   ["10.0.0.0/16 (12.50% used)",
+                    "  10.0.0.0/20 (6.25% used)",
+                    "    10.0.0.0/24 free",
+                    "    10.0.1.0/24",
+                    "    10.0.2.0/23 free",
+                    "    10.0.4.0/22 free",
+                    "    10.0.8.0/21 free",
+                    "  10.0.16.0/20",
+                    "  10.0.32.0/19 free",
+                    "  10.0.64.0/18 free",
+                    "  10.0.128.0/17 free"]);
+        let root = &forest.roots[0];
+        assert_eq!(root.utilization(), 12.5);
+        assert!(root.children.iter().filter(|i| i.gap).all(|i| i.children.is_empty()));
+    }
+}
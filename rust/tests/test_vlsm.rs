@@ -0,0 +1,86 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::vlsm;
+    use ipaddress::vlsm::Requirement;
+    use ipaddress::host_policy::HostPolicy;
+    use num::bigint::BigUint;
+    use num::FromPrimitive;
+
+    fn hosts(n: usize) -> BigUint {
+        BigUint::from_usize(n).unwrap()
+    }
+
+    #[test]
+    pub fn test_prefix_for_hosts() {
+        let ipv4 = IPAddress::parse("10.0.0.0/8").unwrap();
+        assert_eq!(vlsm::prefix_for_hosts(&ipv4, &hosts(500)).unwrap(), 23);
+        assert_eq!(vlsm::prefix_for_hosts(&ipv4, &hosts(510)).unwrap(), 23);
+        assert_eq!(vlsm::prefix_for_hosts(&ipv4, &hosts(511)).unwrap(), 22);
+        assert_eq!(vlsm::prefix_for_hosts(&ipv4, &hosts(62)).unwrap(), 26);
+        assert_eq!(vlsm::prefix_for_hosts(&ipv4, &hosts(2)).unwrap(), 30);
+        assert_eq!(vlsm::prefix_for_hosts(&ipv4, &hosts(1)).unwrap(), 30);
+        assert!(vlsm::prefix_for_hosts(&ipv4, &hosts(0)).is_err());
+        let ipv6 = IPAddress::parse("2001:db8::/32").unwrap();
+        assert_eq!(vlsm::prefix_for_hosts(&ipv6, &hosts(1)).unwrap(), 128);
+        assert_eq!(vlsm::prefix_for_hosts(&ipv6, &hosts(256)).unwrap(), 120);
+    }
+
+    #[test]
+    pub fn test_plan() {
+        let parent = IPAddress::parse("10.20.0.0/20").unwrap();
+        let plan = vlsm::plan(&parent,
+                              &[Requirement::new("branch", 60),
+                                Requirement::new("hq", 500),
+                                Requirement::new("link", 2),
+                                Requirement::new("dc", 120)])
+            .unwrap();
+        let assigned = plan.assignments
+            .iter()
+            .map(|a| format!("{}={}", a.name, a.network.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(assigned,
+                   ["hq=10.20.0.0/23", "dc=10.20.2.0/25", "branch=10.20.2.128/26", "link=10.20.2.192/30"]);
+        assert_eq!(IPAddress::to_string_vec(&plan.free),
+                   ["10.20.2.196/30", "10.20.2.200/29", "10.20.2.208/28", "10.20.2.224/27",
+                    "10.20.3.0/24", "10.20.4.0/22", "10.20.8.0/21"]);
+        assert_eq!(plan.assignments[0].hosts, hosts(500));
+    }
+
+    #[test]
+    pub fn test_plan_ipv6() {
+        let parent = IPAddress::parse("2001:db8::/120").unwrap();
+        let plan = vlsm::plan(&parent, &[Requirement::new("a", 100), Requirement::new("b", 64)]).unwrap();
+        assert_eq!(plan.assignments[0].network.to_string(), "2001:db8::/121");
+        assert_eq!(plan.assignments[1].network.to_string(), "2001:db8::80/122");
+        assert_eq!(IPAddress::to_string_vec(&plan.free), ["2001:db8::c0/122"]);
+    }
+
+    #[test]
+    pub fn test_plan_insufficient() {
+        let parent = IPAddress::parse("192.168.0.0/24").unwrap();
+        let err = vlsm::plan(&parent, &[Requirement::new("a", 200), Requirement::new("b", 100)])
+            .unwrap_err();
+        assert!(err.starts_with("b needs a /25"));
+        assert!(vlsm::plan(&parent, &[Requirement::new("zero", 0)]).is_err());
+        assert!(vlsm::plan(&parent, &[Requirement::new("big", 1000)]).is_err());
+        let exact = vlsm::plan(&parent, &[Requirement::new("a", 126), Requirement::new("b", 126)]).unwrap();
+        assert_eq!(exact.free.len(), 0);
+    }
+
+    #[test]
+    pub fn test_plan_with_policy() {
+        let parent = IPAddress::parse("10.0.0.0/24").unwrap();
+        let cloud = HostPolicy::Reserved { head: 4, tail: 1 };
+        assert_eq!(vlsm::prefix_for_hosts_with(&parent, &hosts(60), &cloud).unwrap(), 25);
+        assert_eq!(vlsm::prefix_for_hosts_with(&parent, &hosts(2), &HostPolicy::Rfc3021).unwrap(), 31);
+        let plan = vlsm::plan_with(&parent, &[Requirement::new("a", 59), Requirement::new("b", 60)], &cloud)
+            .unwrap();
+        assert_eq!(plan.assignments[0].name, "b");
+        assert_eq!(plan.assignments[0].network.to_string(), "10.0.0.0/25");
+        assert_eq!(plan.assignments[1].network.to_string(), "10.0.0.128/26");
+    }
+}
@@ -0,0 +1,80 @@
+extern crate ipaddress;
+extern crate num;
+
+#[cfg(test)]
+mod tests {
+    use ipaddress::IPAddress;
+    use ipaddress::wildcard::Wildcard;
+
+    fn ip(s: &str) -> IPAddress {
+        IPAddress::parse(s).unwrap()
+    }
+
+    fn strings(list: &[IPAddress]) -> Vec<String> {
+        list.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    pub fn test_contiguous() {
+        let acl = Wildcard::parse("10.0.0.7 0.0.0.255").unwrap();
+        assert_eq!(acl.to_string(), "10.0.0.0 0.0.0.255");
+        assert!(acl.is_contiguous());
+        assert_eq!(acl.to_network(), Some(ip("10.0.0.0/24")));
+        assert_eq!(acl.network_count(), 1);
+        assert_eq!(acl.to_networks(), [ip("10.0.0.0/24")]);
+        assert_eq!(Wildcard::from_network(&ip("10.0.0.0/24")).unwrap(), acl);
+        assert!(acl.matches(&ip("10.0.0.200")));
+        assert!(!acl.matches(&ip("10.0.1.0")));
+        assert!(!acl.matches(&ip("::a00:1")));
+
+        let host = Wildcard::parse("host 192.168.1.1").unwrap();
+        assert_eq!(host.to_network(), Some(ip("192.168.1.1/32")));
+        let any: Wildcard = "any".parse().unwrap();
+        assert_eq!(any.to_network(), Some(ip("0.0.0.0/0")));
+        assert!(any.matches(&ip("8.8.8.8")));
+    }
+
+    #[test]
+    pub fn test_non_contiguous() {
+        let acl = Wildcard::parse("10.0.0.0 0.255.0.255").unwrap();
+        assert!(!acl.is_contiguous());
+        assert_eq!(acl.to_network(), None);
+        assert!(acl.matches(&ip("10.20.0.30")));
+        assert!(!acl.matches(&ip("10.20.1.30")));
+        assert_eq!(acl.network_count(), 256);
+        let networks = acl.to_networks();
+        assert_eq!(networks.len(), 256);
+        assert_eq!(networks[1].to_string(), "10.1.0.0/24");
+        assert_eq!(networks[255].to_string(), "10.255.0.0/24");
+
+        let odd = Wildcard::parse("10.0.0.0 0.0.1.3").unwrap();
+        assert_eq!(strings(&odd.to_networks()), ["10.0.0.0/30", "10.0.1.0/30"]);
+        let even = Wildcard::parse("192.168.0.0 0.0.254.0").unwrap();
+        assert_eq!(even.network_count(), 128);
+        assert_eq!(even.to_networks()[1].to_string(), "192.168.2.0/32");
+        assert!(even.matches(&ip("192.168.4.0")) && !even.matches(&ip("192.168.5.0")));
+    }
+
+    #[test]
+    pub fn test_ipv6() {
+        let acl = Wildcard::parse("2001:db8:: 0:0:ffff::ffff:ffff:ffff:ffff").unwrap();
+        assert!(!acl.is_contiguous());
+        assert!(acl.matches(&ip("2001:db8:42::1")));
+        assert!(!acl.matches(&ip("2001:db8:0:1::1")));
+        assert_eq!(acl.network_count(), 1 << 16);
+        let all = Wildcard::from_network(&ip("::/0")).unwrap();
+        assert_eq!(all.to_network(), Some(ip("::/0")));
+        assert_eq!(Wildcard::from_network(&ip("2001:db8::/48")).unwrap().wildcard().to_s(), "::ffff:ffff:ffff:ffff:ffff");
+    }
+
+    #[test]
+    pub fn test_errors() {
+        assert_eq!(Wildcard::parse("10.0.0.0").unwrap_err(), "Invalid wildcard 10.0.0.0");
+        assert_eq!(Wildcard::parse("10.0.0.0 ::ff").unwrap_err(), "10.0.0.0 and ::ff are of different families");
+        assert!(Wildcard::parse("10.0.0.0 0.0.0.256").is_err());
+        assert!(Wildcard::parse("host").is_err());
+        assert_eq!(Wildcard::parse("host 10.0.0.0/24").unwrap_err(), "host needs a single address, got 10.0.0.0/24");
+        assert!(Wildcard::parse("host 2001:db8::/64").is_err());
+        assert!(Wildcard::parse("host 2001:db8::1").is_ok());
+    }
+}